@@ -1,6 +1,10 @@
 use blueprint_sdk::build;
 use blueprint_sdk::tangle::blueprint;
-use hyperlane_validator_blueprint_lib::set_config;
+use hyperlane_validator_blueprint_lib::{
+    checkpoint_audit, checkpoint_lag, confirm_key_rotation, ism_membership, pause_validator,
+    remove_chain, resume_validator, rotate_validator_key, set_checkpoint_syncer, set_config,
+    stop_validator, switch_validator_key, upgrade_agent, validator_address, validator_status,
+};
 use std::path::Path;
 use std::process;
 
@@ -21,7 +25,23 @@ fn main() {
         name: "experiment",
         master_manager_revision: "Latest",
         manager: { Evm = "HyperlaneValidatorBlueprint" },
-        jobs: [set_config]
+        jobs: [
+            set_config,
+            remove_chain,
+            upgrade_agent,
+            validator_address,
+            rotate_validator_key,
+            confirm_key_rotation,
+            set_checkpoint_syncer,
+            checkpoint_audit,
+            checkpoint_lag,
+            ism_membership,
+            validator_status,
+            stop_validator,
+            pause_validator,
+            resume_validator,
+            switch_validator_key
+        ]
     };
 
     match blueprint {
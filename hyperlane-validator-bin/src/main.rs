@@ -1,5 +1,6 @@
 use blueprint_sdk as sdk;
 use hyperlane_validator_blueprint_lib as blueprint;
+use metrics_exporter_prometheus::PrometheusBuilder;
 use sdk::contexts::tangle::TangleClientContext;
 use sdk::crypto::sp_core::SpSr25519;
 use sdk::crypto::tangle_pair_signer::TanglePairSigner;
@@ -9,6 +10,8 @@ use sdk::runner::config::BlueprintEnvironment;
 use sdk::runner::tangle::config::TangleConfig;
 use sdk::tangle::consumer::TangleConsumer;
 use sdk::tangle::producer::TangleProducer;
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
 use tracing_subscriber::filter::LevelFilter;
 
 #[tokio::main]
@@ -36,14 +39,69 @@ async fn main() -> color_eyre::Result<()> {
     // Consumer
     let tangle_consumer = TangleConsumer::new(tangle_client.rpc_client.clone(), sr25519_signer);
 
+    PrometheusBuilder::new()
+        .with_http_listener(metrics_addr()?)
+        .install()?;
+
     let context = blueprint::HyperlaneContext::new(env.clone(), env.data_dir.clone()).await?;
 
+    if context.serves_checkpoints() {
+        let listener = TcpListener::bind(checkpoint_server_addr()?).await?;
+        let context = context.clone();
+        tokio::spawn(async move {
+            if let Err(e) = context.serve_checkpoints(listener).await {
+                sdk::error!("Checkpoint server failed! {e:?}");
+            }
+        });
+    }
+
     sdk::info!("Starting the event watcher ...");
 
     let result = BlueprintRunner::builder(TangleConfig::default(), env)
         .router(
             sdk::Router::new()
                 .route(blueprint::SET_CONFIG_JOB_ID, blueprint::set_config)
+                .route(blueprint::REMOVE_CHAIN_JOB_ID, blueprint::remove_chain)
+                .route(blueprint::UPGRADE_AGENT_JOB_ID, blueprint::upgrade_agent)
+                .route(
+                    blueprint::VALIDATOR_ADDRESS_JOB_ID,
+                    blueprint::validator_address,
+                )
+                .route(
+                    blueprint::ROTATE_VALIDATOR_KEY_JOB_ID,
+                    blueprint::rotate_validator_key,
+                )
+                .route(
+                    blueprint::CONFIRM_KEY_ROTATION_JOB_ID,
+                    blueprint::confirm_key_rotation,
+                )
+                .route(
+                    blueprint::SET_CHECKPOINT_SYNCER_JOB_ID,
+                    blueprint::set_checkpoint_syncer,
+                )
+                .route(
+                    blueprint::CHECKPOINT_AUDIT_JOB_ID,
+                    blueprint::checkpoint_audit,
+                )
+                .route(blueprint::CHECKPOINT_LAG_JOB_ID, blueprint::checkpoint_lag)
+                .route(blueprint::ISM_MEMBERSHIP_JOB_ID, blueprint::ism_membership)
+                .route(
+                    blueprint::VALIDATOR_STATUS_JOB_ID,
+                    blueprint::validator_status,
+                )
+                .route(blueprint::STOP_VALIDATOR_JOB_ID, blueprint::stop_validator)
+                .route(
+                    blueprint::PAUSE_VALIDATOR_JOB_ID,
+                    blueprint::pause_validator,
+                )
+                .route(
+                    blueprint::RESUME_VALIDATOR_JOB_ID,
+                    blueprint::resume_validator,
+                )
+                .route(
+                    blueprint::SWITCH_VALIDATOR_KEY_JOB_ID,
+                    blueprint::switch_validator_key,
+                )
                 .with_context(context),
         )
         .producer(tangle_producer)
@@ -58,6 +116,20 @@ async fn main() -> color_eyre::Result<()> {
     Ok(())
 }
 
+/// The address to serve checkpoints on, from `BIND_ADDR` and `BIND_PORT`
+fn checkpoint_server_addr() -> color_eyre::Result<SocketAddr> {
+    let addr = std::env::var("BIND_ADDR").unwrap_or_else(|_| String::from("0.0.0.0"));
+    let port = std::env::var("BIND_PORT").unwrap_or_else(|_| String::from("9632"));
+    Ok(SocketAddr::new(addr.parse()?, port.parse()?))
+}
+
+/// The address to serve Prometheus metrics on, from `BIND_ADDR` and `METRICS_PORT`
+fn metrics_addr() -> color_eyre::Result<SocketAddr> {
+    let addr = std::env::var("BIND_ADDR").unwrap_or_else(|_| String::from("0.0.0.0"));
+    let port = std::env::var("METRICS_PORT").unwrap_or_else(|_| String::from("9633"));
+    Ok(SocketAddr::new(addr.parse()?, port.parse()?))
+}
+
 pub fn setup_log() {
     use tracing_subscriber::util::SubscriberInitExt;
 
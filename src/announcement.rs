@@ -0,0 +1,76 @@
+//! The validator's announcement on its origin chain's `ValidatorAnnounce`
+//!
+//! Relayers only find a validator's signatures through the storage locations it announced, so a
+//! validator that never announced, or announced somewhere else, is as good as not running.
+
+use crate::config::{self, AgentConfig};
+use blueprint_sdk as sdk;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use sdk::alloy::primitives::Address;
+use sdk::alloy::sol;
+
+sol!(
+    #[sol(rpc)]
+    interface IValidatorAnnounce {
+        function getAnnouncedStorageLocations(address[] calldata validators)
+            external
+            view
+            returns (string[][] memory);
+    }
+);
+
+/// The RPC URL and `ValidatorAnnounce` address of `origin_chain_name` in `config`
+pub fn validator_announce(
+    config: &AgentConfig,
+    origin_chain_name: &str,
+) -> Result<(String, Address)> {
+    let chain = config.chain(origin_chain_name)?;
+    let Some(validator_announce) = &chain.validator_announce else {
+        return Err(eyre!("`{origin_chain_name}` has no `validatorAnnounce`"));
+    };
+
+    let validator_announce = validator_announce
+        .parse()
+        .map_err(|e| eyre!("Invalid `validatorAnnounce` `{validator_announce}`: {e}"))?;
+    Ok((chain.rpc_url()?.to_string(), validator_announce))
+}
+
+/// The storage locations `validator` announced on the `ValidatorAnnounce` at `address`
+pub async fn announced_locations(
+    rpc_url: &str,
+    address: Address,
+    validator: Address,
+) -> Result<Vec<String>> {
+    let provider = config::provider(rpc_url)?;
+    let locations = IValidatorAnnounce::new(address, provider)
+        .getAnnouncedStorageLocations(vec![validator])
+        .call()
+        .await
+        .map_err(|e| eyre!("Failed to read announcements from `{address}`: {e}"))?;
+
+    Ok(locations._0.into_iter().next().unwrap_or_default())
+}
+
+/// Check that `expected` is among the `announced` storage locations
+///
+/// The returned error is the reason the announcement doesn't match.
+pub fn check_announced(expected: &str, announced: &[String]) -> std::result::Result<(), String> {
+    let normalize = |location: &str| location.trim_end_matches('/').to_string();
+    let expected_location = normalize(expected);
+    if announced
+        .iter()
+        .any(|location| normalize(location) == expected_location)
+    {
+        return Ok(());
+    }
+
+    match announced.last() {
+        Some(latest) => Err(format!(
+            "the validator announced `{latest}`, but its checkpoint syncer is `{expected}`"
+        )),
+        None => Err(String::from(
+            "the validator hasn't announced a storage location",
+        )),
+    }
+}
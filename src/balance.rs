@@ -0,0 +1,20 @@
+//! The validator's native balance on its origin chain
+//!
+//! The agent pays for its announcement itself, and without funds it only logs a warning and keeps
+//! retrying. Checking the balance up front tells the service owner which address to fund.
+
+use crate::config;
+use blueprint_sdk as sdk;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use sdk::alloy::primitives::{Address, U256};
+use sdk::alloy::providers::Provider;
+
+/// The native balance of `address`, in wei
+pub async fn balance(rpc_url: &str, address: Address) -> Result<U256> {
+    let provider = config::provider(rpc_url)?;
+    provider
+        .get_balance(address)
+        .await
+        .map_err(|e| eyre!("Failed to get the balance of `{address}`: {e}"))
+}
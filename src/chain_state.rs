@@ -0,0 +1,216 @@
+//! Per-chain state kept on disk, under `chains/<origin_chain_name>/` in the data directory
+//!
+//! Settings are backed up before they're changed, so a validator that fails to start with the new
+//! ones can be put back the way it was. Stopped and paused validators are marked with a file in
+//! their chain's directory, so they stay down across blueprint restarts.
+
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub const AGENT_CONFIGS_DIR: &str = "agent_configs";
+pub const HYPERLANE_DB_DIR: &str = "hyperlane_db";
+pub const AGENT_IMAGE_FILE: &str = "agent_image.txt";
+const STOPPED_MARKER: &str = "stopped";
+const PAUSED_MARKER: &str = "paused";
+
+/// What `stop_validator` does with a chain's `hyperlane_db`
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum DbAction {
+    #[default]
+    Keep,
+    /// Move it aside, named after the current time
+    Archive,
+    Delete,
+}
+
+impl FromStr for DbAction {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "keep" => Ok(Self::Keep),
+            "archive" => Ok(Self::Archive),
+            "delete" => Ok(Self::Delete),
+            _ => Err(eyre!(
+                "Unknown `db` action `{s}`, expected `keep`, `archive` or `delete`"
+            )),
+        }
+    }
+}
+
+/// All origin chains in `chains_dir` with applied configs
+pub fn configured_chains(chains_dir: &Path) -> Result<HashSet<String>> {
+    if !chains_dir.exists() {
+        return Ok(HashSet::new());
+    }
+
+    let mut chains = HashSet::new();
+    for entry in std::fs::read_dir(chains_dir)? {
+        let entry = entry?;
+        if entry.path().join(AGENT_CONFIGS_DIR).exists() {
+            chains.insert(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+
+    Ok(chains)
+}
+
+/// All configured origin chains in `chains_dir` whose validators should be running
+pub fn active_chains(chains_dir: &Path) -> Result<HashSet<String>> {
+    let mut chains = configured_chains(chains_dir)?;
+    chains.retain(|origin_chain_name| {
+        let chain_dir = chains_dir.join(origin_chain_name);
+        !is_stopped(&chain_dir) && !is_paused(&chain_dir)
+    });
+    Ok(chains)
+}
+
+pub fn is_configured(chain_dir: &Path) -> bool {
+    chain_dir.join(AGENT_CONFIGS_DIR).exists()
+}
+
+/// Whether the chain's validator was stopped with `stop_validator`
+pub fn is_stopped(chain_dir: &Path) -> bool {
+    chain_dir.join(STOPPED_MARKER).exists()
+}
+
+/// Whether the chain's validator was paused with `pause_validator`
+pub fn is_paused(chain_dir: &Path) -> bool {
+    chain_dir.join(PAUSED_MARKER).exists()
+}
+
+/// Mark the chain as stopped, replacing any pause
+///
+/// A stopped validator can only be started again with `set_config`.
+pub fn mark_stopped(chain_dir: &Path) -> Result<()> {
+    std::fs::write(
+        chain_dir.join(STOPPED_MARKER),
+        chrono::Utc::now().to_rfc3339(),
+    )?;
+    remove_path(&chain_dir.join(PAUSED_MARKER))
+}
+
+/// The agent image the chain's validator was set to, or `None` if it uses the operator's default
+pub fn agent_image(chain_dir: &Path) -> Result<Option<String>> {
+    let agent_image_path = chain_dir.join(AGENT_IMAGE_FILE);
+    if !agent_image_path.exists() {
+        return Ok(None);
+    }
+
+    let agent_image = std::fs::read_to_string(agent_image_path)?;
+    Ok(Some(agent_image.trim().to_string()))
+}
+
+/// Mark the chain as paused, recording the `agent_image` its container was running
+///
+/// Everything else is left untouched, so the validator resumes with the same image even if the
+/// operator's default changes in the meantime.
+pub fn mark_paused(chain_dir: &Path, agent_image: &str) -> Result<()> {
+    if !is_configured(chain_dir) {
+        return Err(eyre!("No validator is configured for this chain"));
+    }
+
+    if is_stopped(chain_dir) {
+        return Err(eyre!("The validator is stopped, there is nothing to pause"));
+    }
+
+    std::fs::write(chain_dir.join(AGENT_IMAGE_FILE), agent_image)?;
+    std::fs::write(
+        chain_dir.join(PAUSED_MARKER),
+        chrono::Utc::now().to_rfc3339(),
+    )?;
+    Ok(())
+}
+
+pub fn clear_paused(chain_dir: &Path) -> Result<()> {
+    remove_path(&chain_dir.join(PAUSED_MARKER))
+}
+
+/// Clear both markers, e.g. when new configs start the validator again
+pub fn clear_markers(chain_dir: &Path) -> Result<()> {
+    remove_path(&chain_dir.join(STOPPED_MARKER))?;
+    clear_paused(chain_dir)
+}
+
+/// Apply `action` to the chain's `hyperlane_db`
+///
+/// Returns where it was archived to, if it was.
+pub fn apply_db_action(chain_dir: &Path, action: DbAction) -> Result<Option<PathBuf>> {
+    let hyperlane_db_path = chain_dir.join(HYPERLANE_DB_DIR);
+    match action {
+        DbAction::Archive if hyperlane_db_path.exists() => {
+            let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+            let archive_path = chain_dir.join(format!("{HYPERLANE_DB_DIR}.{timestamp}"));
+            std::fs::rename(&hyperlane_db_path, &archive_path)?;
+            Ok(Some(archive_path))
+        }
+        DbAction::Delete => {
+            remove_path(&hyperlane_db_path)?;
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Where `path` is backed up to while a change is being applied
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".orig");
+    path.with_file_name(file_name)
+}
+
+/// Move `path` to its backup location, replacing any previous backup
+pub fn backup(path: &Path) -> Result<()> {
+    let backup_path = backup_path(path);
+    if backup_path.exists() {
+        blueprint_sdk::warn!("Removing old backup at {}", backup_path.display());
+        remove_path(&backup_path)?;
+    }
+
+    if path.exists() {
+        blueprint_sdk::info!("`{}` exists, backing up.", path.display());
+        std::fs::rename(path, backup_path)?;
+    }
+
+    Ok(())
+}
+
+/// Move the backup of `path` back into place
+///
+/// Returns `false` if there is no backup to restore.
+pub fn restore_backup(path: &Path) -> Result<bool> {
+    let backup_path = backup_path(path);
+    if !backup_path.exists() {
+        return Ok(false);
+    }
+
+    blueprint_sdk::debug!("Moving `{}` to `{}`", backup_path.display(), path.display());
+    remove_path(path)?;
+    std::fs::rename(backup_path, path)?;
+    Ok(true)
+}
+
+/// Restore the backup of `path`, or remove `path` if it has none
+///
+/// A setting without a backup didn't exist before the change, so removing it falls back to the
+/// default.
+pub fn revert(path: &Path) -> Result<()> {
+    if !restore_backup(path)? {
+        remove_path(path)?;
+    }
+
+    Ok(())
+}
+
+pub fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)?;
+    } else if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
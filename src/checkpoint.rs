@@ -0,0 +1,101 @@
+//! The checkpoints validators sign, as written by the checkpoint syncer
+
+use blueprint_sdk as sdk;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use sdk::alloy::primitives::{Address, B256, Keccak256, PrimitiveSignature, U256, normalize_v};
+use serde::{Deserialize, Serialize};
+
+/// The file holding the index of the latest signed checkpoint
+pub const LATEST_INDEX_FILE: &str = "checkpoint_latest_index.json";
+/// The file holding the validator's signed announcement
+pub const ANNOUNCEMENT_FILE: &str = "announcement.json";
+/// The file holding the validator's metadata, such as its agent version
+pub const METADATA_FILE: &str = "metadata_latest.json";
+
+/// The file holding the signed checkpoint at `index`
+pub fn checkpoint_file(index: u32) -> String {
+    format!("checkpoint_{index}_with_id.json")
+}
+
+/// A checkpoint and the validator's signature over it, as in `checkpoint_<index>_with_id.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedCheckpointWithMessageId {
+    pub value: CheckpointWithMessageId,
+    pub signature: CheckpointSignature,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointWithMessageId {
+    pub checkpoint: Checkpoint,
+    /// The ID of the message inserted into the tree at `checkpoint.index`
+    pub message_id: B256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub merkle_tree_hook_address: B256,
+    pub mailbox_domain: u32,
+    pub root: B256,
+    pub index: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointSignature {
+    pub r: U256,
+    pub s: U256,
+    pub v: u64,
+}
+
+impl CheckpointWithMessageId {
+    /// The hash validators sign, as an EIP-191 message
+    pub fn signing_hash(&self) -> B256 {
+        let checkpoint = &self.checkpoint;
+
+        let mut domain_hasher = Keccak256::new();
+        domain_hasher.update(checkpoint.mailbox_domain.to_be_bytes());
+        domain_hasher.update(checkpoint.merkle_tree_hook_address);
+        domain_hasher.update(b"HYPERLANE");
+
+        let mut hasher = Keccak256::new();
+        hasher.update(domain_hasher.finalize());
+        hasher.update(checkpoint.root);
+        hasher.update(checkpoint.index.to_be_bytes());
+        hasher.update(self.message_id);
+        hasher.finalize()
+    }
+}
+
+impl SignedCheckpointWithMessageId {
+    /// Parse the contents of `checkpoint_<index>_with_id.json`, checking it holds checkpoint `index`
+    pub fn parse(contents: &[u8], index: u32) -> Result<Self> {
+        let signed: Self = serde_json::from_slice(contents)
+            .map_err(|e| eyre!("Checkpoint {index} is malformed: {e}"))?;
+
+        if signed.value.checkpoint.index != index {
+            return Err(eyre!(
+                "Checkpoint file for index {index} holds checkpoint {}",
+                signed.value.checkpoint.index
+            ));
+        }
+
+        Ok(signed)
+    }
+
+    /// The address that signed the checkpoint
+    pub fn recover_signer(&self) -> Result<Address> {
+        let Some(y_parity) = normalize_v(self.signature.v) else {
+            return Err(eyre!("Invalid signature `v` {}", self.signature.v));
+        };
+
+        let signature = PrimitiveSignature::new(self.signature.r, self.signature.s, y_parity);
+        signature
+            .recover_address_from_msg(self.value.signing_hash())
+            .map_err(|e| eyre!("Failed to recover the checkpoint signer: {e}"))
+    }
+}
+
+/// Parse the contents of `checkpoint_latest_index.json`
+pub fn parse_latest_index(contents: &[u8]) -> Result<u32> {
+    serde_json::from_slice(contents).map_err(|e| eyre!("Latest checkpoint index is malformed: {e}"))
+}
@@ -0,0 +1,136 @@
+//! Auditing the checkpoints validators sign
+//!
+//! Nothing else notices a validator publishing signatures relayers can't use. The newest
+//! checkpoints are read back from wherever the validator publishes them, and each is checked to be
+//! signed by the validator's address, with the latest index never going backwards.
+
+use crate::checkpoint::{self, SignedCheckpointWithMessageId};
+use crate::checkpoint_mirror::Store;
+use blueprint_sdk as sdk;
+use color_eyre::Result;
+use sdk::alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// How many of the newest checkpoints are audited at most on each pass
+pub const AUDIT_WINDOW: u32 = 10;
+
+/// The outcome of auditing a validator's checkpoints so far
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuditStatus {
+    /// Whether the last pass found nothing wrong
+    pub healthy: bool,
+    /// The newest checkpoint index audited
+    pub latest_index: Option<u32>,
+    /// The number of checkpoints audited
+    pub checked: u64,
+    /// The number of problems found
+    pub failures: u64,
+    /// The last problem found, if any
+    pub last_failure: Option<String>,
+    /// When the last pass ran, in seconds since the Unix epoch
+    pub audited_at: u64,
+}
+
+impl AuditStatus {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record the outcome of a pass
+    fn record(&mut self, problems: &[String]) {
+        self.healthy = problems.is_empty();
+        self.failures += problems.len() as u64;
+        if let Some(problem) = problems.last() {
+            self.last_failure = Some(problem.clone());
+        }
+
+        self.audited_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+    }
+}
+
+/// Audit the checkpoints in `source` that haven't been audited yet, returning the problems found
+///
+/// Only the newest [`AUDIT_WINDOW`] checkpoints are read. A checkpoint with a problem still counts
+/// as audited, so each problem is only reported once. Nothing having been published yet isn't a
+/// problem. Errors are only returned if `source` can't be
+/// read, leaving `status` untouched.
+pub async fn audit(
+    source: &Store,
+    expected_signer: Address,
+    status: &mut AuditStatus,
+) -> Result<Vec<String>> {
+    let mut problems = Vec::new();
+
+    let Some(latest_index) = source.get(checkpoint::LATEST_INDEX_FILE).await? else {
+        // Validators only publish once a message has been dispatched from the origin chain
+        status.record(&problems);
+        return Ok(problems);
+    };
+
+    let latest = match checkpoint::parse_latest_index(&latest_index) {
+        Ok(latest) => latest,
+        Err(e) => {
+            problems.push(e.to_string());
+            status.record(&problems);
+            return Ok(problems);
+        }
+    };
+
+    if let Some(previous) = status.latest_index.filter(|previous| latest < *previous) {
+        problems.push(format!(
+            "Latest checkpoint index went backwards, from {previous} to {latest}"
+        ));
+        status.latest_index = Some(latest);
+        status.record(&problems);
+        return Ok(problems);
+    }
+
+    let first = status
+        .latest_index
+        .map_or(0, |index| index + 1)
+        .max(latest.saturating_sub(AUDIT_WINDOW - 1));
+    for index in first..=latest {
+        let Some(contents) = source.get(&checkpoint::checkpoint_file(index)).await? else {
+            problems.push(format!("Checkpoint {index} is missing"));
+            continue;
+        };
+
+        if let Err(e) = audit_checkpoint(&contents, index, expected_signer) {
+            problems.push(e);
+        }
+    }
+
+    if first <= latest {
+        status.checked += u64::from(latest - first + 1);
+        status.latest_index = Some(latest);
+    }
+
+    status.record(&problems);
+    Ok(problems)
+}
+
+fn audit_checkpoint(contents: &[u8], index: u32, expected_signer: Address) -> Result<(), String> {
+    let signed =
+        SignedCheckpointWithMessageId::parse(contents, index).map_err(|e| e.to_string())?;
+    let signer = signed.recover_signer().map_err(|e| e.to_string())?;
+    if signer != expected_signer {
+        return Err(format!(
+            "Checkpoint {index} is signed by `{signer}`, expected `{expected_signer}`"
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! How far a validator's signed checkpoints are behind its origin chain
+//!
+//! Every message dispatched on the origin chain is inserted into the `MerkleTreeHook`, and needs a
+//! checkpoint at its index before it can be relayed. The hook's tree count is compared with the
+//! latest signed checkpoint index, so a validator falling behind is noticed before relays stall.
+
+use crate::config::{self, AgentConfig};
+use blueprint_sdk as sdk;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use sdk::alloy::primitives::Address;
+use sdk::alloy::sol;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+sol!(
+    #[sol(rpc)]
+    interface IMerkleTreeHook {
+        function count() external view returns (uint32);
+    }
+);
+
+/// The last comparison of a validator's checkpoints with its origin chain
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LagStatus {
+    /// The number of messages inserted into the origin chain's `MerkleTreeHook`
+    pub tree_count: u32,
+    /// The index of the latest signed checkpoint, if any
+    pub latest_index: Option<u32>,
+    /// The number of messages without a signed checkpoint
+    pub lag: u32,
+    /// Whether `lag` is above the operator's `max_checkpoint_lag`
+    pub behind: bool,
+    /// When the lag was measured, in seconds since the Unix epoch
+    pub checked_at: u64,
+}
+
+impl LagStatus {
+    pub fn new(tree_count: u32, latest_index: Option<u32>, max_lag: u32) -> Self {
+        let lag = lag(tree_count, latest_index);
+        Self {
+            tree_count,
+            latest_index,
+            lag,
+            behind: lag > max_lag,
+            checked_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&contents)?))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// The number of messages in a tree of `tree_count` that aren't covered by checkpoint
+/// `latest_index`
+pub fn lag(tree_count: u32, latest_index: Option<u32>) -> u32 {
+    let signed = latest_index.map_or(0, |index| index.saturating_add(1));
+    tree_count.saturating_sub(signed)
+}
+
+/// The RPC URL and `MerkleTreeHook` address of `origin_chain_name` in `config`
+pub fn merkle_tree_hook(
+    config: &AgentConfig,
+    origin_chain_name: &str,
+) -> Result<(String, Address)> {
+    let chain = config.chain(origin_chain_name)?;
+    let Some(merkle_tree_hook) = &chain.merkle_tree_hook else {
+        return Err(eyre!("`{origin_chain_name}` has no `merkleTreeHook`"));
+    };
+
+    let merkle_tree_hook = merkle_tree_hook
+        .parse()
+        .map_err(|e| eyre!("Invalid `merkleTreeHook` `{merkle_tree_hook}`: {e}"))?;
+    Ok((chain.rpc_url()?.to_string(), merkle_tree_hook))
+}
+
+/// Read the tree count of the `MerkleTreeHook` at `address`
+pub async fn tree_count(rpc_url: &str, address: Address) -> Result<u32> {
+    let provider = config::provider(rpc_url)?;
+    let count = IMerkleTreeHook::new(address, provider)
+        .count()
+        .call()
+        .await
+        .map_err(|e| eyre!("Failed to read the tree count from `{address}`: {e}"))?;
+    Ok(count._0)
+}
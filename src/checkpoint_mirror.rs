@@ -0,0 +1,359 @@
+//! Mirroring signed checkpoints to extra storage
+//!
+//! Relayers read a validator's signatures from the storage it announced. If that storage goes
+//! down, they can't relay. Operators can configure mirrors, and every validator's checkpoints are
+//! copied to each of them under `<origin_chain_name>/`, in the layout the checkpoint syncer writes.
+//!
+//! Every copy is read back and compared with the original. Progress is kept per chain and mirror,
+//! so a mirror that was unreachable, or a blueprint that was stopped, catches up from where it
+//! left off.
+
+use crate::checkpoint::{self, SignedCheckpointWithMessageId};
+use crate::signer::AwsCredentials;
+use aws_sdk_s3::primitives::ByteStream;
+use blueprint_sdk as sdk;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use sdk::alloy::primitives::{B256, keccak256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Extra storage checkpoints are copied to, configured by the operator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CheckpointMirror {
+    /// A directory on the operator's machine
+    Local { path: PathBuf },
+    /// An S3 bucket, or S3-compatible storage
+    S3 {
+        bucket: String,
+        region: String,
+        #[serde(default)]
+        folder: Option<String>,
+        #[serde(default)]
+        endpoint_url: Option<String>,
+        /// Static credentials for the bucket. If not set, the default AWS credential chain is used.
+        #[serde(default)]
+        credentials: Option<AwsCredentials>,
+    },
+    /// A server accepting `PUT` and `GET` for each file, such as a WebDAV server
+    Http {
+        url: String,
+        /// Sent as the `Authorization` header, if set
+        #[serde(default)]
+        authorization: Option<String>,
+    },
+}
+
+impl CheckpointMirror {
+    /// A stable name for the mirror, used to track its progress
+    pub fn id(&self) -> String {
+        match self {
+            Self::Local { path } => format!("local:{}", path.display()),
+            Self::S3 {
+                bucket,
+                folder,
+                endpoint_url,
+                ..
+            } => format!(
+                "s3:{}/{bucket}/{}",
+                endpoint_url.as_deref().unwrap_or_default(),
+                folder.as_deref().unwrap_or_default()
+            ),
+            Self::Http { url, .. } => format!("http:{url}"),
+        }
+    }
+
+    /// Connect to the mirror, with files for `origin_chain_name` under their own prefix
+    pub async fn store(&self, origin_chain_name: &str) -> Store {
+        match self {
+            Self::Local { path } => Store::Local(path.join(origin_chain_name)),
+            Self::S3 {
+                bucket,
+                region,
+                folder,
+                endpoint_url,
+                credentials,
+            } => {
+                let prefix = match folder {
+                    Some(folder) => format!("{folder}/{origin_chain_name}"),
+                    None => origin_chain_name.to_string(),
+                };
+                Store::S3 {
+                    client: s3_client(region, endpoint_url.as_deref(), credentials.as_ref()).await,
+                    bucket: bucket.clone(),
+                    prefix: Some(prefix),
+                }
+            }
+            Self::Http { url, authorization } => Store::Http {
+                client: reqwest::Client::new(),
+                url: format!("{}/{origin_chain_name}", url.trim_end_matches('/')),
+                authorization: authorization.clone(),
+            },
+        }
+    }
+}
+
+pub(crate) async fn s3_client(
+    region: &str,
+    endpoint_url: Option<&str>,
+    credentials: Option<&AwsCredentials>,
+) -> aws_sdk_s3::Client {
+    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(aws_config::Region::new(region.to_string()));
+    if let Some(credentials) = credentials {
+        loader = loader.credentials_provider(aws_sdk_s3::config::Credentials::new(
+            credentials.access_key_id.clone(),
+            credentials.secret_access_key.clone(),
+            None,
+            None,
+            "operator-config",
+        ));
+    }
+
+    let mut config = aws_sdk_s3::config::Builder::from(&loader.load().await);
+    if let Some(endpoint_url) = endpoint_url {
+        // S3-compatible storage rarely supports virtual-hosted buckets
+        config = config.endpoint_url(endpoint_url).force_path_style(true);
+    }
+
+    aws_sdk_s3::Client::from_conf(config.build())
+}
+
+/// Somewhere checkpoint files can be read from and written to
+pub enum Store {
+    Local(PathBuf),
+    S3 {
+        client: aws_sdk_s3::Client,
+        bucket: String,
+        prefix: Option<String>,
+    },
+    Http {
+        client: reqwest::Client,
+        url: String,
+        authorization: Option<String>,
+    },
+}
+
+impl Store {
+    fn s3_key(prefix: Option<&str>, file_name: &str) -> String {
+        match prefix {
+            Some(prefix) => format!("{prefix}/{file_name}"),
+            None => file_name.to_string(),
+        }
+    }
+
+    /// Read `file_name`, or `None` if it doesn't exist
+    pub async fn get(&self, file_name: &str) -> Result<Option<Vec<u8>>> {
+        match self {
+            Self::Local(path) => match tokio::fs::read(path.join(file_name)).await {
+                Ok(contents) => Ok(Some(contents)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            },
+            Self::S3 {
+                client,
+                bucket,
+                prefix,
+            } => {
+                let key = Self::s3_key(prefix.as_deref(), file_name);
+                let object = match client.get_object().bucket(bucket).key(&key).send().await {
+                    Ok(object) => object,
+                    Err(e) if e.as_service_error().is_some_and(|e| e.is_no_such_key()) => {
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(eyre!("Failed to get `{key}` from `{bucket}`: {e}")),
+                };
+
+                let contents = object.body.collect().await?;
+                Ok(Some(contents.into_bytes().to_vec()))
+            }
+            Self::Http {
+                client,
+                url,
+                authorization,
+            } => {
+                let mut request = client.get(format!("{url}/{file_name}"));
+                if let Some(authorization) = authorization {
+                    request = request.header(reqwest::header::AUTHORIZATION, authorization);
+                }
+
+                let response = request.send().await?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+
+                Ok(Some(response.error_for_status()?.bytes().await?.to_vec()))
+            }
+        }
+    }
+
+    /// Write `contents` to `file_name`, replacing it if it exists
+    pub async fn put(&self, file_name: &str, contents: &[u8]) -> Result<()> {
+        match self {
+            Self::Local(path) => {
+                tokio::fs::create_dir_all(path).await?;
+
+                // Written to a temporary file first, so readers never see a partial file
+                let tmp_path = path.join(format!(".{file_name}.tmp"));
+                tokio::fs::write(&tmp_path, contents).await?;
+                tokio::fs::rename(tmp_path, path.join(file_name)).await?;
+            }
+            Self::S3 {
+                client,
+                bucket,
+                prefix,
+            } => {
+                let key = Self::s3_key(prefix.as_deref(), file_name);
+                client
+                    .put_object()
+                    .bucket(bucket)
+                    .key(&key)
+                    .content_type("application/json")
+                    .body(ByteStream::from(contents.to_vec()))
+                    .send()
+                    .await
+                    .map_err(|e| eyre!("Failed to put `{key}` in `{bucket}`: {e}"))?;
+            }
+            Self::Http {
+                client,
+                url,
+                authorization,
+            } => {
+                let mut request = client
+                    .put(format!("{url}/{file_name}"))
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(contents.to_vec());
+                if let Some(authorization) = authorization {
+                    request = request.header(reqwest::header::AUTHORIZATION, authorization);
+                }
+
+                request.send().await?.error_for_status()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write `contents` to `file_name`, and check the mirror returns exactly what was written
+    async fn put_verified(&self, file_name: &str, contents: &[u8]) -> Result<()> {
+        self.put(file_name, contents).await?;
+
+        let Some(written) = self.get(file_name).await? else {
+            return Err(eyre!("`{file_name}` is missing after writing it"));
+        };
+
+        if keccak256(&written) != keccak256(contents) {
+            return Err(eyre!("`{file_name}` doesn't match what was written"));
+        }
+
+        Ok(())
+    }
+}
+
+/// How far each mirror of a chain has been brought up to date
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MirrorProgress {
+    /// Progress, keyed by [`CheckpointMirror::id`]
+    #[serde(default)]
+    pub mirrors: BTreeMap<String, MirrorState>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MirrorState {
+    /// The highest checkpoint index copied, everything before it has been copied too
+    pub latest_index: Option<u32>,
+    /// Hashes of the other files last copied, so they're only copied again when they change
+    #[serde(default)]
+    pub file_hashes: BTreeMap<String, B256>,
+}
+
+impl MirrorProgress {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// The most checkpoints copied to a mirror in one [`sync`] call, progress is saved after each
+pub const CHECKPOINTS_PER_BATCH: u32 = 100;
+
+/// The most [`sync`] calls for a chain and mirror on each pass, so a mirror catching up on one
+/// chain doesn't hold back the others
+pub const BATCHES_PER_PASS: u32 = 10;
+
+/// Copy up to `max_checkpoints` of what's in `source` that `mirror` doesn't have yet
+///
+/// `state` is updated as files are copied, so progress is kept even if a later file fails. Copying
+/// stops at a checkpoint missing from `source`, since the mirror can't be complete past it. Returns
+/// whether the mirror is caught up.
+pub async fn sync(
+    source: &Store,
+    mirror: &Store,
+    state: &mut MirrorState,
+    max_checkpoints: u32,
+) -> Result<bool> {
+    let Some(latest_index) = source.get(checkpoint::LATEST_INDEX_FILE).await? else {
+        // Nothing signed yet
+        return Ok(true);
+    };
+    let latest = checkpoint::parse_latest_index(&latest_index)?;
+
+    let first = state.latest_index.map_or(0, |index| index + 1);
+    let end = first
+        .saturating_add(max_checkpoints)
+        .min(latest.saturating_add(1));
+    for index in first..end {
+        let file_name = checkpoint::checkpoint_file(index);
+        let Some(contents) = source.get(&file_name).await? else {
+            return Err(eyre!("Checkpoint {index} is missing from the source"));
+        };
+
+        // Don't spread anything that isn't a checkpoint for the right index
+        SignedCheckpointWithMessageId::parse(&contents, index)?;
+        mirror.put_verified(&file_name, &contents).await?;
+        state.latest_index = Some(index);
+    }
+
+    // Only advertise checkpoints the mirror actually has
+    let caught_up = state.latest_index.is_some_and(|index| index >= latest);
+    if caught_up {
+        copy_if_changed(source, mirror, state, checkpoint::LATEST_INDEX_FILE).await?;
+    }
+
+    for file_name in [checkpoint::ANNOUNCEMENT_FILE, checkpoint::METADATA_FILE] {
+        copy_if_changed(source, mirror, state, file_name).await?;
+    }
+
+    Ok(caught_up)
+}
+
+async fn copy_if_changed(
+    source: &Store,
+    mirror: &Store,
+    state: &mut MirrorState,
+    file_name: &str,
+) -> Result<()> {
+    let Some(contents) = source.get(file_name).await? else {
+        return Ok(());
+    };
+
+    let hash = keccak256(&contents);
+    if state.file_hashes.get(file_name) == Some(&hash) {
+        return Ok(());
+    }
+
+    mirror.put_verified(file_name, &contents).await?;
+    state.file_hashes.insert(file_name.to_string(), hash);
+    Ok(())
+}
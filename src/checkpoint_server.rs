@@ -0,0 +1,68 @@
+//! Serving validators' local checkpoints over HTTP
+//!
+//! Validators using the `localStorage` checkpoint syncer write their signatures to the operator's
+//! machine, where relayers elsewhere can't reach them. The server exposes them read-only, in the
+//! layout the checkpoint syncer writes them in, under the origin chain's name:
+//!
+//! * `/<origin_chain_name>/checkpoint_latest_index.json`
+//! * `/<origin_chain_name>/checkpoint_<index>_with_id.json`
+//! * `/<origin_chain_name>/announcement.json`
+//! * `/<origin_chain_name>/metadata_latest.json`
+
+use axum::Router;
+use axum::extract::{Path, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use color_eyre::Result;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// The directory in a chain's data directory holding its local checkpoints
+pub(crate) const CHECKPOINTS_DIR: &str = "checkpoints";
+
+/// Serve the checkpoints of every chain in `chains_dir` on `listener`
+pub async fn serve(listener: TcpListener, chains_dir: PathBuf) -> Result<()> {
+    let app = Router::new()
+        .route("/{origin_chain_name}/{file_name}", get(checkpoint_file))
+        .with_state(Arc::new(chains_dir));
+
+    blueprint_sdk::info!("Serving checkpoints on http://{}", listener.local_addr()?);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn checkpoint_file(
+    State(chains_dir): State<Arc<PathBuf>>,
+    Path((origin_chain_name, file_name)): Path<(String, String)>,
+) -> Response {
+    // Both end up in the path, so anything unexpected is rejected before touching the filesystem
+    if crate::validate_origin_chain_name(&origin_chain_name).is_err()
+        || !is_checkpoint_file(&file_name)
+    {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    let path = chains_dir
+        .join(&origin_chain_name)
+        .join(CHECKPOINTS_DIR)
+        .join(&file_name);
+    match tokio::fs::read(&path).await {
+        Ok(contents) => ([(header::CONTENT_TYPE, "application/json")], contents).into_response(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            blueprint_sdk::warn!("Failed to read `{}`: {e}", path.display());
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Whether `file_name` is something the checkpoint syncer writes
+fn is_checkpoint_file(file_name: &str) -> bool {
+    let Some(stem) = file_name.strip_suffix(".json") else {
+        return false;
+    };
+
+    !stem.is_empty() && stem.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
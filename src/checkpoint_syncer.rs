@@ -0,0 +1,127 @@
+//! Where validators publish their signed checkpoints
+//!
+//! The service owner picks the location with the `set_checkpoint_syncer` job, using the same
+//! format as `checkpointSyncer` in the agent config. Credentials for it are operator secrets,
+//! configured in `operator.json`, and never come from the job.
+
+use crate::config::CheckpointSyncerConfig;
+use crate::signer::AwsCredentials;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The file in a chain's secrets directory holding the GCS service account key
+pub(crate) const GCS_SERVICE_ACCOUNT_KEY_FILE: &str = "gcs_service_account_key.json";
+
+/// Operator-side access to checkpoint storage
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckpointStorageAccess {
+    pub s3: Option<S3Access>,
+    pub gcs: Option<GcsAccess>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct S3Access {
+    /// Static credentials for the bucket. If not set, the default AWS credential chain is used.
+    #[serde(default)]
+    pub credentials: Option<AwsCredentials>,
+    /// Overrides the S3 endpoint, for S3-compatible storage such as MinIO
+    #[serde(default)]
+    pub endpoint_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GcsAccess {
+    /// Path to the service account key on the operator's machine
+    pub service_account_key_path: PathBuf,
+}
+
+/// Parse and check the checkpoint syncer settings given to the `set_checkpoint_syncer` job
+pub fn parse(settings: &str) -> Result<CheckpointSyncerConfig> {
+    let syncer: CheckpointSyncerConfig = serde_json::from_str(settings)
+        .map_err(|e| eyre!("Invalid checkpoint syncer settings: {e}"))?;
+
+    match &syncer {
+        CheckpointSyncerConfig::LocalStorage { path } => {
+            if path.is_empty() {
+                return Err(eyre!("Checkpoint syncer `path` is empty"));
+            }
+        }
+        CheckpointSyncerConfig::S3 { bucket, region, .. } => {
+            if bucket.is_empty() || region.is_empty() {
+                return Err(eyre!(
+                    "S3 checkpoint syncer needs both a `bucket` and a `region`"
+                ));
+            }
+        }
+        CheckpointSyncerConfig::Gcs {
+            bucket,
+            service_account_key,
+            user_secrets,
+            ..
+        } => {
+            if bucket.is_empty() {
+                return Err(eyre!("GCS checkpoint syncer needs a `bucket`"));
+            }
+
+            if service_account_key.is_some() || user_secrets.is_some() {
+                return Err(eyre!(
+                    "GCS credentials are set by the operator, remove `serviceAccountKey` and `userSecrets`"
+                ));
+            }
+        }
+    }
+
+    Ok(syncer)
+}
+
+/// The agent environment selecting `syncer`
+///
+/// The environment takes priority over the config files, so this replaces any checkpoint syncer
+/// from `set_config`.
+pub(crate) fn agent_env(
+    syncer: &CheckpointSyncerConfig,
+    access: &CheckpointStorageAccess,
+) -> Vec<String> {
+    let mut env = Vec::new();
+    match syncer {
+        CheckpointSyncerConfig::LocalStorage { path } => {
+            env.push(String::from("HYP_CHECKPOINTSYNCER_TYPE=localStorage"));
+            env.push(format!("HYP_CHECKPOINTSYNCER_PATH={path}"));
+        }
+        CheckpointSyncerConfig::S3 {
+            bucket,
+            region,
+            folder,
+        } => {
+            env.push(String::from("HYP_CHECKPOINTSYNCER_TYPE=s3"));
+            env.push(format!("HYP_CHECKPOINTSYNCER_BUCKET={bucket}"));
+            env.push(format!("HYP_CHECKPOINTSYNCER_REGION={region}"));
+            if let Some(folder) = folder {
+                env.push(format!("HYP_CHECKPOINTSYNCER_FOLDER={folder}"));
+            }
+
+            let endpoint_url = access.s3.as_ref().and_then(|s3| s3.endpoint_url.as_ref());
+            if let Some(endpoint_url) = endpoint_url {
+                env.push(format!("AWS_ENDPOINT_URL_S3={endpoint_url}"));
+            }
+        }
+        CheckpointSyncerConfig::Gcs { bucket, folder, .. } => {
+            env.push(String::from("HYP_CHECKPOINTSYNCER_TYPE=gcs"));
+            env.push(format!("HYP_CHECKPOINTSYNCER_BUCKET={bucket}"));
+            if let Some(folder) = folder {
+                env.push(format!("HYP_CHECKPOINTSYNCER_FOLDER={folder}"));
+            }
+
+            if access.gcs.is_some() {
+                env.push(format!(
+                    "HYP_CHECKPOINTSYNCER_SERVICEACCOUNTKEY=/secrets/{GCS_SERVICE_ACCOUNT_KEY_FILE}"
+                ));
+            }
+        }
+    }
+
+    env
+}
@@ -0,0 +1,339 @@
+//! Typed model of the Hyperlane agent config
+//!
+//! See <https://docs.hyperlane.xyz/docs/operate/config-reference> for the full format. Only the
+//! parts the blueprint needs to reason about are typed, everything else is preserved in the
+//! `other` maps so configs round-trip without losing fields.
+
+use blueprint_sdk::alloy::primitives::{B256, keccak256};
+use blueprint_sdk::alloy::providers::RootProvider;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// A Hyperlane agent config, as passed to the agent through `CONFIG_FILES`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentConfig {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub chains: BTreeMap<String, ChainConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_rpc_consensus_type: Option<RpcConsensusType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_syncer: Option<CheckpointSyncerConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin_chain_name: Option<String>,
+    /// The signer used by the validator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<SignerConfig>,
+    /// Polling interval for new checkpoints, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_port: Option<u16>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A single entry in [`AgentConfig::chains`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<ChainId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain_id: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rpc_urls: Vec<RpcUrl>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mailbox: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merkle_tree_hook: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator_announce: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interchain_gas_paymaster: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<IndexConfig>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Chain IDs are numeric for EVM chains, but named for others (e.g. Cosmos)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ChainId {
+    Number(u64),
+    Name(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RpcUrl {
+    pub http: String,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IndexConfig {
+    /// The block to start indexing from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<u64>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcConsensusType {
+    Single,
+    Fallback,
+    Quorum,
+}
+
+/// Where the validator publishes its signed checkpoints
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum CheckpointSyncerConfig {
+    LocalStorage {
+        path: String,
+    },
+    S3 {
+        bucket: String,
+        region: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        folder: Option<String>,
+    },
+    Gcs {
+        bucket: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        folder: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        service_account_key: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        user_secrets: Option<String>,
+    },
+}
+
+impl CheckpointSyncerConfig {
+    /// The storage location the validator announces for this checkpoint syncer
+    ///
+    /// This matches what the agent passes to `ValidatorAnnounce.announce`.
+    pub fn storage_location(&self) -> String {
+        match self {
+            Self::LocalStorage { path } => format!("file://{path}"),
+            Self::S3 {
+                bucket,
+                region,
+                folder,
+            } => match folder {
+                Some(folder) => format!("s3://{bucket}/{region}/{folder}"),
+                None => format!("s3://{bucket}/{region}"),
+            },
+            Self::Gcs { bucket, folder, .. } => match folder {
+                Some(folder) => format!("gs://{bucket}/{folder}"),
+                None => format!("gs://{bucket}"),
+            },
+        }
+    }
+}
+
+/// The key an agent signs with
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SignerConfig {
+    HexKey { key: String },
+    Aws { id: String, region: String },
+}
+
+impl core::fmt::Debug for SignerConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::HexKey { .. } => f
+                .debug_struct("HexKey")
+                .field("key", &"<redacted>")
+                .finish(),
+            Self::Aws { id, region } => f
+                .debug_struct("Aws")
+                .field("id", id)
+                .field("region", region)
+                .finish(),
+        }
+    }
+}
+
+/// Contract addresses every chain entry needs for a validator to run against it
+const REQUIRED_CHAIN_ADDRESSES: [&str; 3] = ["mailbox", "merkleTreeHook", "validatorAnnounce"];
+
+impl AgentConfig {
+    /// Parse and merge a set of configs
+    ///
+    /// The agent merges all `CONFIG_FILES` in order, with later files taking precedence, so the
+    /// merge happens on the raw JSON before it is interpreted.
+    pub fn merged(configs: &[String]) -> Result<Self> {
+        let mut merged = Value::Object(Map::new());
+        for (index, config) in configs.iter().enumerate() {
+            let config: Value = serde_json::from_str(config)
+                .map_err(|e| eyre!("Config #{index} is not valid JSON: {e}"))?;
+            if !config.is_object() {
+                return Err(eyre!("Config #{index} is not a JSON object"));
+            }
+
+            // Catch type errors early, so they can be attributed to the right file
+            if let Err(e) = serde_json::from_value::<AgentConfig>(config.clone()) {
+                return Err(eyre!("Config #{index} is not a valid agent config: {e}"));
+            }
+
+            merge(&mut merged, config);
+        }
+
+        serde_json::from_value(merged).map_err(|e| eyre!("Merged config is invalid: {e}"))
+    }
+
+    /// Verify that this config can be used to validate `origin_chain_name`
+    pub fn validate(&self, origin_chain_name: &str) -> Result<()> {
+        if self.chains.is_empty() {
+            return Err(eyre!("Configs do not contain a `chains` map"));
+        }
+
+        if !self.chains.contains_key(origin_chain_name) {
+            return Err(eyre!(
+                "Origin chain `{origin_chain_name}` is not present in the `chains` map"
+            ));
+        }
+
+        for (name, chain) in &self.chains {
+            chain.validate(name)?;
+        }
+
+        Ok(())
+    }
+
+    /// The entry for `name` in the `chains` map
+    pub fn chain(&self, name: &str) -> Result<&ChainConfig> {
+        self.chains
+            .get(name)
+            .ok_or_else(|| eyre!("Chain `{name}` is not present in the `chains` map"))
+    }
+}
+
+/// An HTTP provider for `rpc_url`, e.g. from [`ChainConfig::rpc_url`]
+pub(crate) fn provider(rpc_url: &str) -> Result<RootProvider> {
+    let rpc_url =
+        reqwest::Url::parse(rpc_url).map_err(|e| eyre!("Invalid RPC URL `{rpc_url}`: {e}"))?;
+    Ok(blueprint_sdk::evm::util::get_provider_http(rpc_url))
+}
+
+impl ChainConfig {
+    /// The first of the chain's RPC URLs, which the blueprint uses for its own queries
+    pub fn rpc_url(&self) -> Result<&str> {
+        match self.rpc_urls.first() {
+            Some(rpc_url) => Ok(&rpc_url.http),
+            None => Err(eyre!("Chain has no `rpcUrls` entries")),
+        }
+    }
+
+    fn validate(&self, name: &str) -> Result<()> {
+        let addresses = [
+            &self.mailbox,
+            &self.merkle_tree_hook,
+            &self.validator_announce,
+        ];
+        for (field, address) in REQUIRED_CHAIN_ADDRESSES.iter().zip(addresses) {
+            match address {
+                Some(address) if !address.is_empty() => {}
+                _ => return Err(eyre!("Chain `{name}` is missing `{field}`")),
+            }
+        }
+
+        if self.rpc_urls.is_empty() {
+            return Err(eyre!("Chain `{name}` has no `rpcUrls` entries"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Verify that a set of agent configs can be used to validate `origin_chain_name`
+///
+/// An empty set of configs is valid, as the agent will fall back to its defaults.
+pub fn validate_configs(configs: &[String], origin_chain_name: &str) -> Result<()> {
+    if configs.is_empty() {
+        return Ok(());
+    }
+
+    AgentConfig::merged(configs)?.validate(origin_chain_name)
+}
+
+/// A hash identifying `config` as the config of the validator for `origin_chain_name`
+///
+/// Configs are hashed in their typed form, so formatting and key order don't change the hash.
+pub fn config_hash(config: &AgentConfig, origin_chain_name: &str) -> Result<B256> {
+    let mut preimage = origin_chain_name.as_bytes().to_vec();
+    // Keeps the origin chain name from running into the config
+    preimage.push(0);
+    preimage.extend(serde_json::to_vec(config)?);
+    Ok(keccak256(preimage))
+}
+
+/// Deep merge `other` into `base`, with `other` taking precedence
+fn merge(base: &mut Value, other: Value) {
+    match (base, other) {
+        (Value::Object(base), Value::Object(other)) => {
+            for (key, value) in other {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, other) => *base = other,
+    }
+}
+
+/// Merge the agent configs saved in `dir`, in the order the agent reads them
+pub fn load_merged(dir: &Path) -> Result<AgentConfig> {
+    let mut configs = Vec::new();
+    for path in config_files(dir)? {
+        configs.push(std::fs::read_to_string(path)?);
+    }
+
+    AgentConfig::merged(&configs)
+}
+
+/// The agent config files saved in `dir`, in the order they were given to `set_config`
+///
+/// Files are named after their index, and sorted by it numerically, since `10.json` comes before
+/// `2.json` as a string.
+pub fn config_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+
+    files.sort_by_cached_key(|path| {
+        let index = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<u64>().ok());
+        (index.unwrap_or(u64::MAX), path.clone())
+    });
+    Ok(files)
+}
@@ -0,0 +1,143 @@
+//! Pulling and verifying the Hyperlane agent image
+
+use crate::operator::DEFAULT_AGENT_IMAGE;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use docktopus::bollard::Docker;
+use docktopus::bollard::auth::DockerCredentials;
+use docktopus::bollard::errors::Error as DockerError;
+use docktopus::bollard::image::CreateImageOptions;
+use futures::StreamExt;
+
+/// Make sure `image` is available locally, pulling it if needed
+///
+/// The local image is checked against the digest `image` is pinned to. Unpinned images other than
+/// [`DEFAULT_AGENT_IMAGE`] are refused.
+pub(crate) async fn ensure_image(
+    docker: &Docker,
+    image: &str,
+    credentials: Option<DockerCredentials>,
+) -> Result<()> {
+    if docker.inspect_image(image).await.is_ok() {
+        blueprint_sdk::debug!("Image `{image}` already present, skipping pull");
+    } else {
+        pull_image(docker, image, credentials).await?;
+    }
+
+    verify_digest(docker, image).await
+}
+
+/// The digest an image reference is pinned to, e.g. `sha256:...` for `repo@sha256:...`
+pub(crate) fn pinned_digest(image: &str) -> Option<&str> {
+    image
+        .split_once('@')
+        .map(|(_, digest)| digest)
+        .filter(|digest| !digest.is_empty())
+}
+
+async fn verify_digest(docker: &Docker, image: &str) -> Result<()> {
+    let Some(expected) = pinned_digest(image) else {
+        if image == DEFAULT_AGENT_IMAGE {
+            blueprint_sdk::warn!(
+                "Image `{image}` is not pinned by digest, it will not be verified"
+            );
+            return Ok(());
+        }
+
+        return Err(eyre!(
+            "Image `{image}` is not pinned by digest (`<repo>@sha256:<digest>`). Refusing to run it"
+        ));
+    };
+
+    let inspect = docker.inspect_image(image).await?;
+    let repo_digests = inspect.repo_digests.unwrap_or_default();
+
+    let matches = repo_digests
+        .iter()
+        .filter_map(|repo_digest| repo_digest.split_once('@'))
+        .any(|(_, digest)| digest == expected);
+    if !matches {
+        return Err(eyre!(
+            "Image `{image}` does not match its pinned digest, found {repo_digests:?}. Refusing to run it"
+        ));
+    }
+
+    blueprint_sdk::debug!("Image `{image}` matches its pinned digest");
+    Ok(())
+}
+
+async fn pull_image(
+    docker: &Docker,
+    image: &str,
+    credentials: Option<DockerCredentials>,
+) -> Result<()> {
+    blueprint_sdk::info!("Pulling image `{image}`");
+
+    let mut pull = docker.create_image(
+        Some(CreateImageOptions {
+            from_image: image,
+            ..Default::default()
+        }),
+        None,
+        credentials,
+    );
+
+    let mut last_status = None;
+    while let Some(info) = pull.next().await {
+        let info = info.map_err(|e| pull_error(image, &e))?;
+
+        if let Some(error) = info.error {
+            return Err(pull_error_message(image, &error));
+        }
+
+        let Some(status) = info.status else {
+            continue;
+        };
+
+        // Per-layer progress is noisy, only surface it when debugging
+        match (&info.id, &info.progress) {
+            (Some(id), Some(progress)) => blueprint_sdk::debug!("{id}: {status} {progress}"),
+            (Some(id), None) => blueprint_sdk::debug!("{id}: {status}"),
+            (None, _) => {
+                if last_status.as_ref() != Some(&status) {
+                    blueprint_sdk::info!("{status}");
+                }
+            }
+        }
+
+        last_status = Some(status);
+    }
+
+    blueprint_sdk::info!("Pulled image `{image}`");
+    Ok(())
+}
+
+fn pull_error(image: &str, error: &DockerError) -> color_eyre::Report {
+    match error {
+        DockerError::DockerResponseServerError { message, .. } => {
+            pull_error_message(image, message)
+        }
+        DockerError::DockerStreamError { error } => pull_error_message(image, error),
+        e => eyre!("Failed to pull image `{image}`: {e}"),
+    }
+}
+
+/// The error for a failed pull of `image`, pointing at `registry_auth` if the registry refused the
+/// credentials
+pub fn pull_error_message(image: &str, message: &str) -> color_eyre::Report {
+    const AUTH_ERRORS: [&str; 4] = [
+        "unauthorized",
+        "denied",
+        "authentication required",
+        "no basic auth credentials",
+    ];
+
+    let lowercase = message.to_lowercase();
+    if AUTH_ERRORS.iter().any(|e| lowercase.contains(e)) {
+        return eyre!(
+            "Registry authentication failed while pulling `{image}`, check `registry_auth` in the operator config: {message}"
+        );
+    }
+
+    eyre!("Failed to pull image `{image}`: {message}")
+}
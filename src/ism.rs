@@ -0,0 +1,255 @@
+//! Whether the ISMs on the destination chains trust our validator
+//!
+//! A validator only matters if a multisig ISM lists it. For each destination chain, the mailbox's
+//! default ISM is resolved for messages from the origin chain, through any routing and aggregation
+//! ISMs, down to the multisig ISMs and their validator sets.
+
+use crate::config::{self, AgentConfig};
+use blueprint_sdk as sdk;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use sdk::alloy::primitives::{Address, Bytes};
+use sdk::alloy::providers::RootProvider;
+use sdk::alloy::sol;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+sol!(
+    #[sol(rpc)]
+    interface IMailbox {
+        function defaultIsm() external view returns (address);
+    }
+
+    #[sol(rpc)]
+    interface IInterchainSecurityModule {
+        function moduleType() external view returns (uint8);
+    }
+
+    #[sol(rpc)]
+    interface IRoutingIsm {
+        function route(bytes calldata message) external view returns (address);
+    }
+
+    #[sol(rpc)]
+    interface IAggregationIsm {
+        function modulesAndThreshold(bytes calldata message)
+            external
+            view
+            returns (address[] memory, uint8);
+    }
+
+    #[sol(rpc)]
+    interface IMultisigIsm {
+        function validatorsAndThreshold(bytes calldata message)
+            external
+            view
+            returns (address[] memory, uint8);
+    }
+);
+
+/// `IInterchainSecurityModule.Types` values the check understands
+const ROUTING: u8 = 1;
+const AGGREGATION: u8 = 2;
+const LEGACY_MULTISIG: u8 = 3;
+const MERKLE_ROOT_MULTISIG: u8 = 4;
+const MESSAGE_ID_MULTISIG: u8 = 5;
+
+/// The most ISMs followed for a single destination, in case of a routing loop
+pub const MAX_ISMS: usize = 16;
+
+/// A multisig ISM on a destination chain
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MultisigIsm {
+    pub address: Address,
+    pub threshold: u8,
+    pub validators: Vec<Address>,
+}
+
+/// Our validator's standing with the ISMs of a destination chain
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct IsmMembership {
+    /// Whether any of `isms` lists our validator
+    pub member: bool,
+    /// The multisig ISMs that verify messages from the origin chain
+    pub isms: Vec<MultisigIsm>,
+    /// Why the ISMs couldn't be fully read, if they couldn't
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Check every destination chain in `config` for `validator`, keyed by destination chain
+///
+/// Every chain other than `origin_chain_name` counts as a destination.
+pub async fn check_membership(
+    config: &AgentConfig,
+    origin_chain_name: &str,
+    validator: Address,
+) -> Result<BTreeMap<String, IsmMembership>> {
+    let Some(origin_domain) = config.chain(origin_chain_name)?.domain_id else {
+        return Err(eyre!("`{origin_chain_name}` has no `domainId`"));
+    };
+
+    let mut memberships = BTreeMap::new();
+    for (destination, chain) in &config.chains {
+        if destination == origin_chain_name {
+            continue;
+        }
+
+        let isms = match chain.rpc_url() {
+            Ok(rpc_url) => {
+                let message = message(origin_domain, chain.domain_id);
+                multisig_isms(rpc_url, chain.mailbox.as_deref(), &message).await
+            }
+            Err(e) => Err(e),
+        };
+
+        let membership = match isms {
+            Ok(isms) => IsmMembership {
+                member: isms.iter().any(|ism| ism.validators.contains(&validator)),
+                isms,
+                error: None,
+            },
+            Err(e) => IsmMembership {
+                error: Some(e.to_string()),
+                ..Default::default()
+            },
+        };
+        memberships.insert(destination.clone(), membership);
+    }
+
+    Ok(memberships)
+}
+
+/// A message from `origin_domain`, which is all routing ISMs look at
+///
+/// A missing `destination_domain` is left as 0, since routing ISMs don't read it.
+pub fn message(origin_domain: u32, destination_domain: Option<u32>) -> Bytes {
+    let mut message = Vec::with_capacity(77);
+    // Version
+    message.push(3);
+    // Nonce
+    message.extend_from_slice(&0u32.to_be_bytes());
+    message.extend_from_slice(&origin_domain.to_be_bytes());
+    // Sender
+    message.extend_from_slice(&[0; 32]);
+    message.extend_from_slice(&destination_domain.unwrap_or_default().to_be_bytes());
+    // Recipient
+    message.extend_from_slice(&[0; 32]);
+    Bytes::from(message)
+}
+
+/// What an ISM does with a message, as far as the check is concerned
+#[derive(Debug, Clone, PartialEq)]
+pub enum Module {
+    /// Hands the message to another ISM
+    Routing(Address),
+    /// Requires a threshold of other ISMs
+    Aggregation(Vec<Address>),
+    Multisig {
+        threshold: u8,
+        validators: Vec<Address>,
+    },
+    /// Doesn't rely on validators, or isn't supported yet
+    Other,
+}
+
+/// Resolve the ISM at `root` down to its multisig ISMs, reading each ISM on the way with `read`
+pub async fn resolve<F, Fut>(root: Address, mut read: F) -> Result<Vec<MultisigIsm>>
+where
+    F: FnMut(Address) -> Fut,
+    Fut: Future<Output = Result<Module>>,
+{
+    let mut isms = Vec::new();
+    let mut pending = vec![root];
+    let mut followed = 0;
+    while let Some(ism) = pending.pop() {
+        followed += 1;
+        if followed > MAX_ISMS {
+            return Err(eyre!("Gave up after following {MAX_ISMS} ISMs"));
+        }
+
+        match read(ism).await? {
+            Module::Routing(routed) => pending.push(routed),
+            Module::Aggregation(modules) => pending.extend(modules),
+            Module::Multisig {
+                threshold,
+                validators,
+            } => isms.push(MultisigIsm {
+                address: ism,
+                threshold,
+                validators,
+            }),
+            Module::Other => {}
+        }
+    }
+
+    Ok(isms)
+}
+
+/// Resolve the default ISM of the mailbox at `mailbox` down to its multisig ISMs
+async fn multisig_isms(
+    rpc_url: &str,
+    mailbox: Option<&str>,
+    message: &Bytes,
+) -> Result<Vec<MultisigIsm>> {
+    let Some(mailbox) = mailbox else {
+        return Err(eyre!("Chain has no `mailbox`"));
+    };
+    let mailbox: Address = mailbox
+        .parse()
+        .map_err(|e| eyre!("Invalid `mailbox` `{mailbox}`: {e}"))?;
+
+    let provider = config::provider(rpc_url)?;
+
+    let default_ism = IMailbox::new(mailbox, provider.clone())
+        .defaultIsm()
+        .call()
+        .await
+        .map_err(|e| eyre!("Failed to read the default ISM of `{mailbox}`: {e}"))?
+        ._0;
+
+    resolve(default_ism, |ism| read_module(&provider, ism, message)).await
+}
+
+/// Read what the ISM at `ism` does with `message`, based on its `moduleType`
+async fn read_module(provider: &RootProvider, ism: Address, message: &Bytes) -> Result<Module> {
+    let module_type = IInterchainSecurityModule::new(ism, provider.clone())
+        .moduleType()
+        .call()
+        .await
+        .map_err(|e| eyre!("Failed to read the type of ISM `{ism}`: {e}"))?
+        ._0;
+
+    let module = match module_type {
+        ROUTING => {
+            let routed = IRoutingIsm::new(ism, provider.clone())
+                .route(message.clone())
+                .call()
+                .await
+                .map_err(|e| eyre!("Failed to route through ISM `{ism}`: {e}"))?;
+            Module::Routing(routed._0)
+        }
+        AGGREGATION => {
+            let modules = IAggregationIsm::new(ism, provider.clone())
+                .modulesAndThreshold(message.clone())
+                .call()
+                .await
+                .map_err(|e| eyre!("Failed to read the modules of ISM `{ism}`: {e}"))?;
+            Module::Aggregation(modules._0)
+        }
+        LEGACY_MULTISIG | MERKLE_ROOT_MULTISIG | MESSAGE_ID_MULTISIG => {
+            let validators = IMultisigIsm::new(ism, provider.clone())
+                .validatorsAndThreshold(message.clone())
+                .call()
+                .await
+                .map_err(|e| eyre!("Failed to read the validators of ISM `{ism}`: {e}"))?;
+            Module::Multisig {
+                threshold: validators._1,
+                validators: validators._0,
+            }
+        }
+        _ => Module::Other,
+    };
+
+    Ok(module)
+}
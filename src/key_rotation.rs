@@ -0,0 +1,185 @@
+//! Validator keys generated by key rotations
+//!
+//! Rotated keys live in their own keystore in the data directory, so they never mix with the
+//! operator's identity keys. Which of them is in use is tracked in `validator_keys.json`.
+//!
+//! A rotation goes through three steps: [`rotate`] generates a pending key, [`switch`] makes it
+//! the current key once it's funded, and [`confirm`] deletes the previous key.
+
+use blueprint_sdk as sdk;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use sdk::alloy::signers::local::PrivateKeySigner;
+use sdk::crypto::sp_core::{SpEcdsa, SpEcdsaPublic};
+use sdk::crypto::tangle_pair_signer::TanglePairSigner;
+use sdk::keystore::backends::Backend;
+use sdk::keystore::{Keystore, KeystoreConfig};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const KEYSTORE_DIR: &str = "validator_keystore";
+const STATE_FILE: &str = "validator_keys.json";
+const PENDING_FILE: &str = "validator_key_pending.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotatedKeys {
+    /// The key validators sign with
+    pub current: SpEcdsaPublic,
+    /// The key from before the last rotation, kept until the rotation is confirmed
+    ///
+    /// `None` if the previous key wasn't a rotated key, and so doesn't need to be kept here.
+    pub previous: Option<SpEcdsaPublic>,
+    /// Whether the last rotation is still waiting for the service owner to confirm it
+    pub pending_confirmation: bool,
+}
+
+impl RotatedKeys {
+    /// Load the rotation state from `data_dir`, if the key was ever rotated
+    pub fn load(data_dir: &Path) -> Result<Option<Self>> {
+        let path = state_path(data_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(&path)?;
+        let keys = serde_json::from_str(&contents)
+            .map_err(|e| eyre!("Invalid validator key state at `{}`: {e}", path.display()))?;
+        Ok(Some(keys))
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        std::fs::write(state_path(data_dir), serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Forget about rotations entirely, going back to the key chosen by the operator config
+    pub fn clear(data_dir: &Path) -> Result<()> {
+        let path = state_path(data_dir);
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn state_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(STATE_FILE)
+}
+
+fn pending_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(PENDING_FILE)
+}
+
+/// The key generated by [`rotate`] that validators haven't switched to yet, if any
+pub fn pending(data_dir: &Path) -> Result<Option<SpEcdsaPublic>> {
+    let path = pending_path(data_dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents = std::fs::read_to_string(&path)?;
+    let key = serde_json::from_str(&contents)
+        .map_err(|e| eyre!("Invalid pending validator key at `{}`: {e}", path.display()))?;
+    Ok(Some(key))
+}
+
+fn save_pending(data_dir: &Path, key: &SpEcdsaPublic) -> Result<()> {
+    std::fs::write(pending_path(data_dir), serde_json::to_vec_pretty(key)?)?;
+    Ok(())
+}
+
+/// Generate the key for the next rotation
+///
+/// Validators keep signing with their current key until [`switch`] is called.
+pub fn rotate(data_dir: &Path) -> Result<SpEcdsaPublic> {
+    if RotatedKeys::load(data_dir)?.is_some_and(|keys| keys.pending_confirmation) {
+        return Err(eyre!(
+            "The last key rotation hasn't been confirmed yet, confirm it before rotating again"
+        ));
+    }
+
+    let keystore = keystore(data_dir)?;
+    if let Some(pending) = pending(data_dir)? {
+        return Err(eyre!(
+            "Validator key `{}` is already waiting to be switched to",
+            signer(&keystore, &pending)?.address()
+        ));
+    }
+
+    let key = generate(&keystore)?;
+    save_pending(data_dir, &key)?;
+    Ok(key)
+}
+
+/// Make the pending key the one validators sign with
+///
+/// Returns the rotation state from before, to [`rollback`] to if validators fail to start with
+/// the new key.
+pub fn switch(data_dir: &Path) -> Result<Option<RotatedKeys>> {
+    let Some(key) = pending(data_dir)? else {
+        return Err(eyre!(
+            "No validator key is waiting to be switched to, rotate the key first"
+        ));
+    };
+
+    let previous = RotatedKeys::load(data_dir)?;
+    RotatedKeys {
+        current: key,
+        previous: previous.as_ref().map(|keys| keys.current.clone()),
+        pending_confirmation: true,
+    }
+    .save(data_dir)?;
+    std::fs::remove_file(pending_path(data_dir))?;
+
+    Ok(previous)
+}
+
+/// Undo a [`switch`], going back to `previous`
+///
+/// The new key stays pending, so the switch can be retried.
+pub fn rollback(data_dir: &Path, previous: Option<RotatedKeys>) -> Result<()> {
+    if let Some(keys) = RotatedKeys::load(data_dir)? {
+        save_pending(data_dir, &keys.current)?;
+    }
+
+    match previous {
+        Some(keys) => keys.save(data_dir),
+        None => RotatedKeys::clear(data_dir),
+    }
+}
+
+/// Confirm the last [`switch`], deleting the previous key
+///
+/// Returns the key validators now sign with.
+pub fn confirm(data_dir: &Path) -> Result<SpEcdsaPublic> {
+    let Some(mut keys) = RotatedKeys::load(data_dir)?.filter(|keys| keys.pending_confirmation)
+    else {
+        return Err(eyre!("No key rotation is waiting to be confirmed"));
+    };
+
+    if let Some(previous) = keys.previous.take() {
+        keystore(data_dir)?.remove::<SpEcdsa>(&previous)?;
+    }
+
+    keys.pending_confirmation = false;
+    keys.save(data_dir)?;
+    Ok(keys.current)
+}
+
+/// The keystore holding rotated validator keys
+pub fn keystore(data_dir: &Path) -> Result<Keystore> {
+    let keystore = Keystore::new(KeystoreConfig::new().fs_root(data_dir.join(KEYSTORE_DIR)))?;
+    Ok(keystore)
+}
+
+/// Generate a new validator key
+fn generate(keystore: &Keystore) -> Result<SpEcdsaPublic> {
+    Ok(keystore.generate::<SpEcdsa>(None)?)
+}
+
+/// Load the signer for `public` from `keystore`
+pub fn signer(keystore: &Keystore, public: &SpEcdsaPublic) -> Result<PrivateKeySigner> {
+    let pair = keystore.get_secret::<SpEcdsa>(public)?;
+    Ok(TanglePairSigner::new(pair.0).alloy_key()?)
+}
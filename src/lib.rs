@@ -1,19 +1,58 @@
+pub mod announcement;
+mod balance;
+pub mod chain_state;
+pub mod checkpoint;
+pub mod checkpoint_audit;
+pub mod checkpoint_lag;
+pub mod checkpoint_mirror;
+pub mod checkpoint_server;
+pub mod checkpoint_syncer;
+pub mod config;
+pub mod image;
+pub mod ism;
+pub mod key_rotation;
+pub mod operator;
+pub mod readiness;
+pub mod signer;
+pub mod status;
+
 use blueprint_sdk as sdk;
+use chain_state::{DbAction, backup, backup_path, remove_path, restore_backup, revert};
+use checkpoint_audit::AuditStatus;
+use checkpoint_lag::LagStatus;
+use checkpoint_mirror::{MirrorProgress, Store};
+use checkpoint_syncer::GCS_SERVICE_ACCOUNT_KEY_FILE;
 use color_eyre::Result;
 use color_eyre::eyre::eyre;
+use config::{AgentConfig, CheckpointSyncerConfig};
 use docktopus::DockerBuilder;
+use docktopus::bollard::container::{
+    Config, CreateContainerOptions, ListContainersOptions, RemoveContainerOptions,
+};
+use docktopus::bollard::models::HostConfig;
 use docktopus::bollard::network::ConnectNetworkOptions;
 use docktopus::container::Container;
+use key_rotation::RotatedKeys;
+use operator::{LowBalanceAction, OperatorConfig};
+use sdk::alloy::primitives::{Address, B256};
+use sdk::alloy::signers::local::PrivateKeySigner;
 use sdk::crypto::sp_core::SpEcdsa;
 use sdk::crypto::tangle_pair_signer::TanglePairSigner;
 use sdk::extract::Context;
 use sdk::keystore::backends::Backend;
 use sdk::macros::context::{ServicesContext, TangleClientContext};
 use sdk::runner::config::BlueprintEnvironment;
-use sdk::tangle::extract::{List, Optional, TangleArgs2, TangleResult};
+use sdk::tangle::extract::{List, Optional, TangleArg, TangleArgs2, TangleResult};
+use serde::{Deserialize, Serialize};
+use signer::ValidatorSigner;
+use status::ValidatorStatus;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
 use tokio::sync::Mutex;
 
 pub fn default_data_dir() -> PathBuf {
@@ -27,47 +66,229 @@ pub struct HyperlaneContext {
     pub env: BlueprintEnvironment,
     data_dir: PathBuf,
     connection: Arc<DockerBuilder>,
-    container: Arc<Mutex<Option<String>>>,
+    operator_config: Arc<OperatorConfig>,
+    /// Validator container IDs, keyed by origin chain
+    containers: Arc<Mutex<HashMap<String, String>>>,
+    /// Locks keeping a validator from being started twice at once, keyed by origin chain
+    spinup_locks: Arc<Mutex<HashMap<String, Arc<Mutex<()>>>>>,
 }
 
-const IMAGE: &str = "gcr.io/abacus-labs-dev/hyperlane-agent:agents-v1.2.0";
+const BLUEPRINT_ID_LABEL: &str = "tangle.blueprint_id";
+const SERVICE_ID_LABEL: &str = "tangle.service_id";
+const ORIGIN_CHAIN_LABEL: &str = "hyperlane.origin_chain";
+
+/// The image validators ran before multiple origin chains were supported
+const LEGACY_AGENT_IMAGE: &str = "gcr.io/abacus-labs-dev/hyperlane-agent:agents-v1.2.0";
+
+/// The file in a chain's secrets directory holding the agent's AWS credentials
+const AWS_CREDENTIALS_FILE: &str = "aws_credentials";
+
+/// How often checkpoint mirrors are brought up to date
+const CHECKPOINT_MIRROR_INTERVAL: Duration = Duration::from_secs(30);
+/// How often validators' newest checkpoints are audited
+const CHECKPOINT_AUDIT_INTERVAL: Duration = Duration::from_secs(60);
+/// How often validators' checkpoints are compared with their origin chains
+const CHECKPOINT_LAG_INTERVAL: Duration = Duration::from_secs(60);
+
 impl HyperlaneContext {
     pub async fn new(env: BlueprintEnvironment, data_dir: PathBuf) -> Result<Self> {
         let connection = DockerBuilder::new().await?;
-        Ok(Self {
+        let operator_config = OperatorConfig::load(&data_dir.join(OperatorConfig::FILE_NAME))?;
+        let ctx = Self {
             env,
             data_dir,
             connection: Arc::new(connection),
-            container: Arc::new(Mutex::new(None)),
-        })
+            operator_config: Arc::new(operator_config),
+            containers: Arc::new(Mutex::new(HashMap::new())),
+            spinup_locks: Arc::new(Mutex::new(HashMap::new())),
+        };
+
+        ctx.migrate_legacy_layout().await?;
+        ctx.reconcile_containers().await?;
+
+        // Mirrors keep every validator's checkpoints, while only running validators are audited
+        // and measured
+        if !ctx.operator_config.checkpoint_mirrors.is_empty() {
+            tokio::spawn(ctx.clone().every_chain(
+                "mirror checkpoints",
+                CHECKPOINT_MIRROR_INTERVAL,
+                Self::configured_chains,
+                Self::mirror_chain_checkpoints,
+            ));
+        }
+
+        tokio::spawn(ctx.clone().every_chain(
+            "audit checkpoints",
+            CHECKPOINT_AUDIT_INTERVAL,
+            Self::active_chains,
+            Self::audit_chain_checkpoints,
+        ));
+        tokio::spawn(ctx.clone().every_chain(
+            "measure the checkpoint lag",
+            CHECKPOINT_LAG_INTERVAL,
+            Self::active_chains,
+            Self::measure_checkpoint_lag,
+        ));
+
+        Ok(ctx)
     }
 
-    #[tracing::instrument(skip_all)]
-    async fn spinup_container(&self) -> Result<()> {
-        let mut container_guard = self.container.lock().await;
-        if container_guard.is_some() {
+    /// Adopt validator containers left behind by a previous run, and restart any missing ones
+    ///
+    /// Containers are matched to this service by their labels. Any chain that has configs
+    /// applied, but no running container, is started again from its last applied configs, unless
+    /// it was stopped or paused. Those validators are started in the background, all at once, so
+    /// their readiness checks don't hold up the blueprint.
+    async fn reconcile_containers(&self) -> Result<()> {
+        let Some(labels) = self.service_labels() else {
+            blueprint_sdk::warn!("Service ID is unknown, skipping container reconciliation");
             return Ok(());
-        }
+        };
 
-        blueprint_sdk::info!("Spinning up new container");
+        let filters = HashMap::from([(
+            String::from("label"),
+            labels
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>(),
+        )]);
+        let existing = self
+            .connection
+            .client()
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                filters,
+                ..Default::default()
+            }))
+            .await?;
 
-        // TODO: Bollard isn't pulling the image for some reason?
-        let output = Command::new("docker").args(["pull", IMAGE]).output()?;
-        if !output.status.success() {
-            return Err(eyre!("Docker pull failed"));
+        let active_chains = self.active_chains()?;
+
+        let missing_chains = {
+            let mut containers = self.containers.lock().await;
+            for summary in existing {
+                let Some(id) = summary.id else {
+                    continue;
+                };
+
+                let origin_chain_name = summary
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.get(ORIGIN_CHAIN_LABEL));
+                let running = summary.state.as_deref() == Some("running");
+
+                match origin_chain_name {
+                    Some(origin_chain_name)
+                        if running
+                            && active_chains.contains(origin_chain_name)
+                            && !containers.contains_key(origin_chain_name) =>
+                    {
+                        blueprint_sdk::info!(
+                            "Adopting existing container `{id}` for `{origin_chain_name}`"
+                        );
+                        containers.insert(origin_chain_name.clone(), id);
+                    }
+                    _ => {
+                        // Exited, duplicate, stopped, paused, or no longer configured. Anything
+                        // that should be running is recreated below.
+                        blueprint_sdk::warn!("Removing stale container `{id}`");
+                        let mut c = Container::from_id(self.connection.client(), id).await?;
+                        c.remove(Some(RemoveContainerOptions {
+                            force: true,
+                            ..Default::default()
+                        }))
+                        .await?;
+                    }
+                }
+            }
+
+            active_chains
+                .into_iter()
+                .filter(|origin_chain_name| !containers.contains_key(origin_chain_name))
+                .collect::<Vec<_>>()
+        };
+
+        for origin_chain_name in missing_chains {
+            let ctx = self.clone();
+            tokio::spawn(async move {
+                blueprint_sdk::info!("Restoring validator for `{origin_chain_name}`");
+                if let Err(e) = ctx.spinup_container(&origin_chain_name).await {
+                    blueprint_sdk::error!(
+                        "Failed to restore validator for `{origin_chain_name}`: {e}"
+                    );
+                }
+            });
         }
 
-        let mut container = Container::new(self.connection.client(), IMAGE);
+        Ok(())
+    }
 
-        let keystore = self.env.keystore();
-        let ecdsa_pub = keystore.first_local::<SpEcdsa>()?;
-        let ecdsa_pair = keystore.get_secret::<SpEcdsa>(&ecdsa_pub)?;
-        let tangle_ecdsa_pair = TanglePairSigner::new(ecdsa_pair.0);
+    /// Labels identifying the containers belonging to this service
+    ///
+    /// Returns `None` if the service ID isn't known.
+    fn service_labels(&self) -> Option<HashMap<String, String>> {
+        let settings = self.env.protocol_settings.tangle().ok()?;
+        let service_id = settings.service_id?;
+
+        Some(HashMap::from([
+            (
+                BLUEPRINT_ID_LABEL.to_string(),
+                settings.blueprint_id.to_string(),
+            ),
+            (SERVICE_ID_LABEL.to_string(), service_id.to_string()),
+        ]))
+    }
+
+    /// All origin chains with applied configs
+    fn configured_chains(&self) -> Result<HashSet<String>> {
+        chain_state::configured_chains(&self.chains_dir())
+    }
+
+    /// All configured origin chains whose validators should be running
+    fn active_chains(&self) -> Result<HashSet<String>> {
+        chain_state::active_chains(&self.chains_dir())
+    }
+
+    /// Whether the validator for `origin_chain_name` was stopped with `stop_validator`
+    fn is_stopped(&self, origin_chain_name: &str) -> bool {
+        chain_state::is_stopped(&self.chain_dir(origin_chain_name))
+    }
+
+    /// Whether the validator for `origin_chain_name` was paused with `pause_validator`
+    fn is_paused(&self, origin_chain_name: &str) -> bool {
+        chain_state::is_paused(&self.chain_dir(origin_chain_name))
+    }
+
+    /// Start the validator for `origin_chain_name`, and wait for it to be ready
+    ///
+    /// Only this chain is locked while the validator starts, the other chains and `containers` stay
+    /// available.
+    #[tracing::instrument(skip(self))]
+    async fn spinup_container(&self, origin_chain_name: &str) -> Result<()> {
+        let spinup_lock = self
+            .spinup_locks
+            .lock()
+            .await
+            .entry(origin_chain_name.to_string())
+            .or_default()
+            .clone();
+        let _spinup_guard = spinup_lock.lock().await;
+
+        if self.containers.lock().await.contains_key(origin_chain_name) {
+            return Ok(());
+        }
+
+        blueprint_sdk::info!("Spinning up new container");
 
-        let alloy_key = tangle_ecdsa_pair.alloy_key()?;
-        let secret = hex::encode(alloy_key.to_bytes());
+        let agent_image = self.agent_image(origin_chain_name)?;
+        image::ensure_image(
+            &self.connection.client(),
+            &agent_image,
+            self.operator_config.registry_credentials(),
+        )
+        .await?;
 
-        let hyperlane_db_path = self.hyperlane_db_path();
+        let hyperlane_db_path = self.hyperlane_db_path(origin_chain_name);
         if !hyperlane_db_path.exists() {
             blueprint_sdk::warn!("Hyperlane DB does not exist, creating...");
             std::fs::create_dir_all(&hyperlane_db_path)?;
@@ -76,7 +297,7 @@ impl HyperlaneContext {
 
         let mut binds = vec![format!("{}:/hyperlane_db", hyperlane_db_path.display())];
 
-        let agent_configs_path = self.agent_configs_path();
+        let agent_configs_path = self.agent_configs_path(origin_chain_name);
         let agent_configs_path_exists = agent_configs_path.exists();
         if agent_configs_path_exists {
             binds.push(format!(
@@ -85,41 +306,77 @@ impl HyperlaneContext {
             ));
         }
 
-        let mut env = Vec::new();
+        let checkpoint_syncer = self.checkpoint_syncer(origin_chain_name)?;
+        // Keep the checkpoints on the host, so the checkpoint server, mirrors and monitoring can
+        // reach them
+        let local_checkpoints_path =
+            self.local_checkpoints_path(origin_chain_name, checkpoint_syncer.as_ref())?;
+        if let Some(local_checkpoints_path) = local_checkpoints_path {
+            let checkpoints_path = self.checkpoints_path(origin_chain_name);
+            std::fs::create_dir_all(&checkpoints_path)?;
+            binds.push(format!(
+                "{}:{local_checkpoints_path}",
+                checkpoints_path.display()
+            ));
+        }
 
+        let mut config_files = Vec::new();
         if agent_configs_path_exists {
-            let mut config_files = Vec::new();
-
-            let files = std::fs::read_dir(agent_configs_path)?;
-            for config in files {
-                let path = config?.path();
-                if path.is_file() {
-                    config_files.push(format!(
-                        "/config/{}",
-                        path.file_name().unwrap().to_string_lossy()
-                    ));
-                }
-            }
-
-            if !config_files.is_empty() {
-                env.push(format!("CONFIG_FILES={}", config_files.join(",")));
+            for path in config::config_files(&agent_configs_path)? {
+                config_files.push(format!(
+                    "/config/{}",
+                    path.file_name().unwrap().to_string_lossy()
+                ));
             }
         }
 
-        let origin_chain_name_path = self.origin_chain_name_path();
-        if origin_chain_name_path.exists() {
-            let origin_chain_name = std::fs::read_to_string(origin_chain_name_path)?;
-            env.push(format!("HYP_ORIGINCHAINNAME={origin_chain_name}"));
+        let secrets_path = self.secrets_path(origin_chain_name);
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&secrets_path)?;
+        binds.push(format!("{}:/secrets:ro", secrets_path.display()));
+
+        let (signer_config_files, signer_env) = self.configure_signer(&secrets_path).await?;
+        config_files.extend(signer_config_files);
+
+        let mut env = vec![format!("HYP_ORIGINCHAINNAME={origin_chain_name}")];
+        // An empty `CONFIG_FILES` is read as a single empty path, so the agent wouldn't start
+        if !config_files.is_empty() {
+            env.push(format!("CONFIG_FILES={}", config_files.join(",")));
         }
+        env.extend(signer_env);
+        env.extend(self.configure_checkpoint_syncer(checkpoint_syncer.as_ref(), &secrets_path)?);
+        env.extend(self.configure_aws_credentials(checkpoint_syncer.as_ref(), &secrets_path)?);
 
-        container = container.env(env).binds(binds).cmd([
-            "./validator",
-            "--db /hyperlane_db",
-            "--validator.key",
-            &format!("0x{secret}"),
-        ]);
+        let mut labels = self.service_labels().unwrap_or_default();
+        labels.insert(
+            ORIGIN_CHAIN_LABEL.to_string(),
+            origin_chain_name.to_string(),
+        );
+
+        let config = Config {
+            image: Some(agent_image),
+            env: Some(env),
+            cmd: Some(vec![
+                String::from("./validator"),
+                String::from("--db /hyperlane_db"),
+            ]),
+            labels: Some(labels),
+            host_config: Some(HostConfig {
+                binds: Some(binds),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
 
-        container.create().await?;
+        // Created through bollard directly, as `Container` has no way to set labels
+        let response = self
+            .connection
+            .client()
+            .create_container(None::<CreateContainerOptions<String>>, config)
+            .await?;
+        let mut container = Container::from_id(self.connection.client(), response.id).await?;
 
         if self.env.test_mode {
             let id = container.id().unwrap();
@@ -136,61 +393,731 @@ impl HyperlaneContext {
         }
 
         container.start(false).await?;
-        *container_guard = container.id().map(ToString::to_string);
+        let Some(id) = container.id().map(ToString::to_string) else {
+            return Err(eyre!("Container has no ID after starting"));
+        };
+        self.containers
+            .lock()
+            .await
+            .insert(origin_chain_name.to_string(), id.clone());
 
-        // Allow time to spin up
-        tokio::time::sleep(std::time::Duration::from_secs(20)).await;
+        readiness::wait_for_validator(
+            &self.connection.client(),
+            &id,
+            self.operator_config.readiness_timeout(),
+        )
+        .await?;
 
-        let status = container.status().await?;
+        self.verify_announcement(origin_chain_name, checkpoint_syncer.as_ref())
+            .await?;
 
-        // Container is down, something's wrong.
-        if !status.unwrap().is_active() {
-            return Err(eyre!("Failed to start container, config error?"));
+        if self.operator_config.check_ism_membership {
+            self.log_ism_membership(origin_chain_name).await;
         }
 
         Ok(())
     }
 
-    async fn revert_configs(&self) -> Result<()> {
-        blueprint_sdk::error!("Container failed to start with new configs, reverting");
+    /// Whether the ISMs on each destination chain of `origin_chain_name` trust the validator
+    async fn ism_membership(
+        &self,
+        origin_chain_name: &str,
+    ) -> Result<BTreeMap<String, ism::IsmMembership>> {
+        let config = self.applied_config(origin_chain_name)?;
+        let validator = self.validator_address().await?;
+        ism::check_membership(&config, origin_chain_name, validator).await
+    }
 
-        self.remove_existing_container().await?;
+    async fn log_ism_membership(&self, origin_chain_name: &str) {
+        let memberships = match self.ism_membership(origin_chain_name).await {
+            Ok(memberships) => memberships,
+            Err(e) => {
+                blueprint_sdk::warn!("Unable to check ISMs for `{origin_chain_name}`: {e}");
+                return;
+            }
+        };
 
-        let original_configs_path = self.original_agent_configs_path();
-        if !original_configs_path.exists() {
-            // There is no config to revert
-            return Err(eyre!("Configs failed to apply, with no fallback"));
+        for (destination, membership) in memberships {
+            if let Some(e) = membership.error {
+                blueprint_sdk::warn!("Unable to check ISMs on `{destination}`: {e}");
+            } else if membership.member {
+                blueprint_sdk::info!(
+                    "ISMs on `{destination}` trust the validator for `{origin_chain_name}`"
+                );
+            } else {
+                blueprint_sdk::warn!(
+                    "No ISM on `{destination}` trusts the validator for `{origin_chain_name}`, its signatures won't be used there"
+                );
+            }
         }
+    }
 
-        let configs_path = self.agent_configs_path();
+    /// The [`config::config_hash`] of the configs the validator for `origin_chain_name` is running
+    /// with, or `None` if it isn't running
+    async fn running_config_hash(&self, origin_chain_name: &str) -> Result<Option<B256>> {
+        let container_id = self.containers.lock().await.get(origin_chain_name).cloned();
+        let Some(container_id) = container_id else {
+            return Ok(None);
+        };
 
-        blueprint_sdk::debug!(
-            "Moving `{}` to `{}`",
-            original_configs_path.display(),
-            configs_path.display()
+        let client = self.connection.client();
+        let running = status::container_state(&client, &container_id)
+            .await
+            .is_some_and(|state| state.state == "running");
+        if !running {
+            return Ok(None);
+        }
+
+        let config = self.applied_config(origin_chain_name)?;
+        Ok(Some(config::config_hash(&config, origin_chain_name)?))
+    }
+
+    /// What's running for `origin_chain_name`
+    async fn validator_status(
+        &self,
+        origin_chain_name: &str,
+        validator_address: &str,
+    ) -> Result<ValidatorStatus> {
+        let config = self.applied_config(origin_chain_name)?;
+        let container_state = if self.is_stopped(origin_chain_name) {
+            "stopped"
+        } else if self.is_paused(origin_chain_name) {
+            "paused"
+        } else {
+            "missing"
+        };
+        let mut status = ValidatorStatus {
+            config_hash: config::config_hash(&config, origin_chain_name)?.to_string(),
+            container_state: String::from(container_state),
+            image: self.agent_image(origin_chain_name)?,
+            validator_address: String::from(validator_address),
+            ..Default::default()
+        };
+
+        let container_id = self.containers.lock().await.get(origin_chain_name).cloned();
+        if let Some(container_id) = container_id {
+            let client = self.connection.client();
+            if let Some(state) = status::container_state(&client, &container_id).await {
+                status.container_state = state.state;
+                status.uptime_secs = state.uptime_secs;
+                status.image = state.image.unwrap_or(status.image);
+            }
+        }
+
+        // Checkpoints are informational, the rest of the status is still useful without them
+        let latest_index = match self.checkpoint_source(origin_chain_name).await {
+            Ok(Some(source)) => source
+                .get(checkpoint::LATEST_INDEX_FILE)
+                .await
+                .ok()
+                .flatten(),
+            _ => None,
+        };
+        status.latest_checkpoint_index = latest_index
+            .and_then(|latest_index| checkpoint::parse_latest_index(&latest_index).ok());
+
+        Ok(status)
+    }
+
+    /// Check that `address` has enough funds on `origin_chain_name` to announce the validator
+    ///
+    /// Depending on the operator config, a low balance either fails or only logs a warning. The
+    /// balance being unreadable never fails, since the agent may reach RPCs the blueprint can't.
+    async fn check_validator_balance(
+        &self,
+        config: &AgentConfig,
+        origin_chain_name: &str,
+        address: Address,
+    ) -> Result<()> {
+        let rpc_url = config.chain(origin_chain_name)?.rpc_url()?;
+        let balance = match balance::balance(rpc_url, address).await {
+            Ok(balance) => balance,
+            Err(e) => {
+                blueprint_sdk::warn!("Unable to check the validator's balance: {e}");
+                return Ok(());
+            }
+        };
+
+        let min_balance = self.operator_config.min_validator_balance;
+        if balance >= min_balance {
+            blueprint_sdk::info!(
+                "Validator `{address}` has {balance} wei on `{origin_chain_name}`"
+            );
+            return Ok(());
+        }
+
+        let message = format!(
+            "Validator `{address}` has {balance} wei on `{origin_chain_name}`, below the minimum of {min_balance} wei. Fund `{address}` for it to announce itself"
         );
-        std::fs::remove_dir_all(&configs_path)?;
-        std::fs::rename(original_configs_path, configs_path)?;
-
-        let original_origin_chain_name_path = self.original_origin_chain_name_path();
-        if original_origin_chain_name_path.exists() {
-            let origin_chain_name_path = self.origin_chain_name_path();
-            blueprint_sdk::debug!(
-                "Moving `{}` to `{}`",
-                original_origin_chain_name_path.display(),
-                origin_chain_name_path.display(),
+        match self.operator_config.low_balance {
+            LowBalanceAction::Fail => Err(eyre!(message)),
+            LowBalanceAction::Warn => {
+                blueprint_sdk::warn!("{message}");
+                Ok(())
+            }
+        }
+    }
+
+    /// Check that the validator for `origin_chain_name` announced its checkpoint syncer's location
+    ///
+    /// The check needs the origin chain's `validatorAnnounce` and RPC from the applied configs. With
+    /// the default configs, or configs missing either, it's skipped with a warning rather than
+    /// failing a validator that is already running.
+    async fn verify_announcement(
+        &self,
+        origin_chain_name: &str,
+        checkpoint_syncer: Option<&CheckpointSyncerConfig>,
+    ) -> Result<()> {
+        let Some(checkpoint_syncer) =
+            self.effective_checkpoint_syncer(origin_chain_name, checkpoint_syncer)?
+        else {
+            return Ok(());
+        };
+
+        let config = self.applied_config(origin_chain_name)?;
+        let (rpc_url, validator_announce) =
+            match announcement::validator_announce(&config, origin_chain_name) {
+                Ok(validator_announce) => validator_announce,
+                Err(e) => {
+                    blueprint_sdk::warn!(
+                        "Unable to verify the announcement of `{origin_chain_name}`, skipping: {e}"
+                    );
+                    return Ok(());
+                }
+            };
+        let validator = self.validator_address().await?;
+        let expected_location = checkpoint_syncer.storage_location();
+
+        readiness::wait_for_announcement(
+            &rpc_url,
+            validator_announce,
+            validator,
+            &expected_location,
+            self.operator_config.readiness_timeout(),
+        )
+        .await?;
+
+        blueprint_sdk::info!(
+            "Validator `{validator}` for `{origin_chain_name}` announced `{expected_location}`"
+        );
+        Ok(())
+    }
+
+    /// Write the secrets for the operator's chosen signer into `secrets_path`
+    ///
+    /// Returns the extra config files (relative to the container) and environment the agent needs.
+    /// Secrets are passed in files, rather than the command line or environment, so they don't show
+    /// up in `ps` or `docker inspect`.
+    async fn configure_signer(&self, secrets_path: &Path) -> Result<(Vec<String>, Vec<String>)> {
+        match &self.operator_config.signer {
+            ValidatorSigner::Derived | ValidatorSigner::Keystore => {
+                let validator_key = self.validator_key()?;
+                blueprint_sdk::info!("Validator signs as `{}`", validator_key.address());
+
+                // Listed last, so user configs can't override it
+                let config_files = signer::write_key_secret(&validator_key, secrets_path)?;
+                Ok((config_files, Vec::new()))
+            }
+            ValidatorSigner::AwsKms(kms) => {
+                // Don't leave a raw key around from before switching to KMS
+                signer::remove_key_secret(secrets_path)?;
+
+                // Fail early if the key is missing or unusable, the agent would only crash later
+                let address = kms.address().await?;
+                blueprint_sdk::info!(
+                    "Validator signs as `{address}` with KMS key `{}`",
+                    kms.key_id
+                );
+
+                Ok((Vec::new(), kms.agent_env()))
+            }
+        }
+    }
+
+    /// Write the secrets for the checkpoint syncer set with `set_checkpoint_syncer` into
+    /// `secrets_path`
+    ///
+    /// Returns the environment the agent needs, empty if the syncer comes from the configs.
+    fn configure_checkpoint_syncer(
+        &self,
+        checkpoint_syncer: Option<&CheckpointSyncerConfig>,
+        secrets_path: &Path,
+    ) -> Result<Vec<String>> {
+        let gcs_key_path = secrets_path.join(GCS_SERVICE_ACCOUNT_KEY_FILE);
+        let access = &self.operator_config.checkpoint_storage;
+
+        match (checkpoint_syncer, &access.gcs) {
+            (Some(CheckpointSyncerConfig::Gcs { .. }), Some(gcs)) => {
+                let key = std::fs::read(&gcs.service_account_key_path).map_err(|e| {
+                    eyre!(
+                        "Unable to read the GCS service account key at `{}`: {e}",
+                        gcs.service_account_key_path.display()
+                    )
+                })?;
+                write_secret(&gcs_key_path, &key)?;
+            }
+            _ => remove_path(&gcs_key_path)?,
+        }
+
+        Ok(checkpoint_syncer
+            .map(|syncer| checkpoint_syncer::agent_env(syncer, access))
+            .unwrap_or_default())
+    }
+
+    /// Write the AWS credentials for the KMS signer and S3 checkpoint syncer into `secrets_path`
+    ///
+    /// The agent reads both from the same credential chain, so only one set of credentials can be
+    /// used. Returns the environment the agent needs.
+    fn configure_aws_credentials(
+        &self,
+        checkpoint_syncer: Option<&CheckpointSyncerConfig>,
+        secrets_path: &Path,
+    ) -> Result<Vec<String>> {
+        let aws_credentials_path = secrets_path.join(AWS_CREDENTIALS_FILE);
+
+        let signer_credentials = match &self.operator_config.signer {
+            ValidatorSigner::AwsKms(kms) => kms.credentials.as_ref(),
+            _ => None,
+        };
+        let syncer_credentials = match checkpoint_syncer {
+            Some(CheckpointSyncerConfig::S3 { .. }) => self
+                .operator_config
+                .checkpoint_storage
+                .s3
+                .as_ref()
+                .and_then(|s3| s3.credentials.as_ref()),
+            _ => None,
+        };
+
+        let credentials = match (signer_credentials, syncer_credentials) {
+            (Some(signer), Some(syncer)) if signer != syncer => {
+                return Err(eyre!(
+                    "The KMS signer and S3 checkpoint storage have different AWS credentials, the agent can only use one set"
+                ));
+            }
+            (signer, syncer) => signer.or(syncer),
+        };
+
+        let Some(credentials) = credentials else {
+            remove_path(&aws_credentials_path)?;
+            return Ok(Vec::new());
+        };
+
+        write_secret(
+            &aws_credentials_path,
+            credentials.shared_credentials_file().as_bytes(),
+        )?;
+        Ok(vec![format!(
+            "AWS_SHARED_CREDENTIALS_FILE=/secrets/{AWS_CREDENTIALS_FILE}"
+        )])
+    }
+
+    /// The configs applied to `origin_chain_name`, merged into one
+    fn applied_config(&self, origin_chain_name: &str) -> Result<AgentConfig> {
+        let agent_configs_path = self.agent_configs_path(origin_chain_name);
+        if !agent_configs_path.exists() {
+            return Ok(AgentConfig::default());
+        }
+
+        config::load_merged(&agent_configs_path)
+    }
+
+    /// The checkpoint syncer the validator for `origin_chain_name` uses, either from
+    /// `set_checkpoint_syncer` or its configs
+    fn effective_checkpoint_syncer(
+        &self,
+        origin_chain_name: &str,
+        checkpoint_syncer: Option<&CheckpointSyncerConfig>,
+    ) -> Result<Option<CheckpointSyncerConfig>> {
+        match checkpoint_syncer {
+            Some(checkpoint_syncer) => Ok(Some(checkpoint_syncer.clone())),
+            None => Ok(self.applied_config(origin_chain_name)?.checkpoint_syncer),
+        }
+    }
+
+    /// Where the validator for `origin_chain_name` writes its checkpoints inside its container, if
+    /// it uses the `localStorage` checkpoint syncer
+    fn local_checkpoints_path(
+        &self,
+        origin_chain_name: &str,
+        checkpoint_syncer: Option<&CheckpointSyncerConfig>,
+    ) -> Result<Option<String>> {
+        let checkpoint_syncer =
+            self.effective_checkpoint_syncer(origin_chain_name, checkpoint_syncer)?;
+        let Some(CheckpointSyncerConfig::LocalStorage { path }) = checkpoint_syncer else {
+            return Ok(None);
+        };
+
+        if !Path::new(&path).is_absolute() {
+            blueprint_sdk::warn!(
+                "Checkpoint syncer path `{path}` for `{origin_chain_name}` is relative, its checkpoints won't be served or mirrored"
             );
-            std::fs::rename(original_origin_chain_name_path, origin_chain_name_path)?;
+            return Ok(None);
+        }
+
+        Ok(Some(path))
+    }
+
+    /// Whether the operator enabled the checkpoint server
+    pub fn serves_checkpoints(&self) -> bool {
+        self.operator_config.serve_checkpoints
+    }
+
+    /// Serve the local checkpoints of this service's validators on `listener`
+    ///
+    /// See [`checkpoint_server`] for the layout.
+    pub async fn serve_checkpoints(&self, listener: TcpListener) -> Result<()> {
+        checkpoint_server::serve(listener, self.chains_dir()).await
+    }
+
+    /// Where the blueprint can read the checkpoints of the validator for `origin_chain_name`
+    ///
+    /// Returns `None` if its checkpoint syncer isn't readable by the blueprint. Local checkpoints
+    /// are only readable while the validator's container has them mounted from the host, otherwise
+    /// whatever is there is stale.
+    async fn checkpoint_source(&self, origin_chain_name: &str) -> Result<Option<Store>> {
+        let checkpoint_syncer = self.checkpoint_syncer(origin_chain_name)?;
+        let checkpoint_syncer =
+            self.effective_checkpoint_syncer(origin_chain_name, checkpoint_syncer.as_ref())?;
+
+        let source = match checkpoint_syncer {
+            Some(CheckpointSyncerConfig::LocalStorage { path }) => {
+                let checkpoints_path = self.checkpoints_path(origin_chain_name);
+                let container_id = self.containers.lock().await.get(origin_chain_name).cloned();
+                let mounted = match container_id {
+                    Some(container_id) => {
+                        status::has_mount(
+                            &self.connection.client(),
+                            &container_id,
+                            &checkpoints_path,
+                            &path,
+                        )
+                        .await
+                    }
+                    None => false,
+                };
+
+                mounted.then_some(Store::Local(checkpoints_path))
+            }
+            Some(CheckpointSyncerConfig::S3 {
+                bucket,
+                region,
+                folder,
+            }) => {
+                let access = self.operator_config.checkpoint_storage.s3.as_ref();
+                let client = checkpoint_mirror::s3_client(
+                    &region,
+                    access.and_then(|s3| s3.endpoint_url.as_deref()),
+                    access.and_then(|s3| s3.credentials.as_ref()),
+                )
+                .await;
+                Some(Store::S3 {
+                    client,
+                    bucket,
+                    prefix: folder,
+                })
+            }
+            Some(CheckpointSyncerConfig::Gcs { .. }) | None => None,
+        };
+
+        Ok(source)
+    }
+
+    /// Run `pass` on every chain listed by `chains` every `interval`, until the blueprint stops
+    ///
+    /// A chain failing its pass is logged, and tried again on the next one.
+    async fn every_chain(
+        self,
+        task: &str,
+        interval: Duration,
+        chains: impl Fn(&Self) -> Result<HashSet<String>>,
+        pass: impl AsyncFn(&Self, &str) -> Result<()>,
+    ) {
+        loop {
+            match chains(&self) {
+                Ok(chains) => {
+                    for origin_chain_name in chains {
+                        if let Err(e) = pass(&self, &origin_chain_name).await {
+                            blueprint_sdk::warn!("Failed to {task} for `{origin_chain_name}`: {e}");
+                        }
+                    }
+                }
+                Err(e) => blueprint_sdk::error!("Failed to list chains to {task}: {e}"),
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn mirror_chain_checkpoints(&self, origin_chain_name: &str) -> Result<()> {
+        let Some(source) = self.checkpoint_source(origin_chain_name).await? else {
+            return Ok(());
+        };
+
+        let progress_path = self.mirror_progress_path(origin_chain_name);
+        let mut progress = MirrorProgress::load(&progress_path)?;
+        for mirror in &self.operator_config.checkpoint_mirrors {
+            let store = mirror.store(origin_chain_name).await;
+
+            for _ in 0..checkpoint_mirror::BATCHES_PER_PASS {
+                let state = progress.mirrors.entry(mirror.id()).or_default();
+                let synced = checkpoint_mirror::sync(
+                    &source,
+                    &store,
+                    state,
+                    checkpoint_mirror::CHECKPOINTS_PER_BATCH,
+                )
+                .await;
+                progress.save(&progress_path)?;
+
+                match synced {
+                    Ok(true) => break,
+                    Ok(false) => {}
+                    // One unreachable mirror shouldn't hold back the others, it catches up on a
+                    // later pass
+                    Err(e) => {
+                        blueprint_sdk::warn!(
+                            "Mirror `{}` for `{origin_chain_name}` is behind: {e}",
+                            mirror.id()
+                        );
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn audit_chain_checkpoints(&self, origin_chain_name: &str) -> Result<()> {
+        let Some(source) = self.checkpoint_source(origin_chain_name).await? else {
+            return Ok(());
+        };
+
+        let expected_signer = self.validator_address().await?;
+
+        let status_path = self.checkpoint_audit_path(origin_chain_name);
+        let mut status = AuditStatus::load(&status_path)?;
+        let problems = checkpoint_audit::audit(&source, expected_signer, &mut status).await?;
+        for problem in &problems {
+            blueprint_sdk::error!("Checkpoint audit for `{origin_chain_name}` failed: {problem}");
+        }
+
+        let labels = [("origin", origin_chain_name.to_string())];
+        metrics::counter!(
+            "hyperlane_validator_checkpoint_audit_failures_total",
+            &labels
+        )
+        .increment(problems.len() as u64);
+        metrics::gauge!("hyperlane_validator_checkpoint_audit_healthy", &labels)
+            .set(if status.healthy { 1.0 } else { 0.0 });
+        if let Some(latest_index) = status.latest_index {
+            metrics::gauge!("hyperlane_validator_checkpoint_audit_latest_index", &labels)
+                .set(f64::from(latest_index));
+        }
+
+        status.save(&status_path)
+    }
+
+    /// The latest audit of every validator's checkpoints, keyed by origin chain
+    fn checkpoint_audits(&self) -> Result<BTreeMap<String, AuditStatus>> {
+        let mut audits = BTreeMap::new();
+        for origin_chain_name in self.configured_chains()? {
+            let status = AuditStatus::load(&self.checkpoint_audit_path(&origin_chain_name))?;
+            audits.insert(origin_chain_name, status);
+        }
+
+        Ok(audits)
+    }
+
+    async fn measure_checkpoint_lag(&self, origin_chain_name: &str) -> Result<()> {
+        let Some(source) = self.checkpoint_source(origin_chain_name).await? else {
+            // Unreadable checkpoints can't be measured, so don't keep alerting on an old measurement
+            let status_path = self.checkpoint_lag_path(origin_chain_name);
+            if status_path.exists() {
+                remove_path(&status_path)?;
+                let labels = [("origin", origin_chain_name.to_string())];
+                metrics::gauge!("hyperlane_validator_checkpoint_behind", &labels).set(0.0);
+            }
+
+            return Ok(());
+        };
+
+        let config = self.applied_config(origin_chain_name)?;
+        let (rpc_url, merkle_tree_hook) =
+            checkpoint_lag::merkle_tree_hook(&config, origin_chain_name)?;
+        let tree_count = checkpoint_lag::tree_count(&rpc_url, merkle_tree_hook).await?;
+
+        let latest_index = match source.get(checkpoint::LATEST_INDEX_FILE).await? {
+            Some(latest_index) => Some(checkpoint::parse_latest_index(&latest_index)?),
+            None => None,
+        };
+
+        let status = LagStatus::new(
+            tree_count,
+            latest_index,
+            self.operator_config.max_checkpoint_lag,
+        );
+        if status.behind {
+            blueprint_sdk::warn!(
+                "Validator for `{origin_chain_name}` is {} messages behind, with {tree_count} in the tree",
+                status.lag
+            );
+        }
+
+        let labels = [("origin", origin_chain_name.to_string())];
+        metrics::gauge!("hyperlane_validator_checkpoint_lag", &labels).set(f64::from(status.lag));
+        metrics::gauge!("hyperlane_validator_merkle_tree_count", &labels)
+            .set(f64::from(tree_count));
+        metrics::gauge!("hyperlane_validator_checkpoint_behind", &labels).set(if status.behind {
+            1.0
+        } else {
+            0.0
+        });
+
+        status.save(&self.checkpoint_lag_path(origin_chain_name))
+    }
+
+    /// The last measured lag of every validator, keyed by origin chain
+    ///
+    /// Chains whose lag was never measured are left out.
+    fn checkpoint_lags(&self) -> Result<BTreeMap<String, LagStatus>> {
+        let mut lags = BTreeMap::new();
+        for origin_chain_name in self.configured_chains()? {
+            if let Some(status) = LagStatus::load(&self.checkpoint_lag_path(&origin_chain_name))? {
+                lags.insert(origin_chain_name, status);
+            }
+        }
+
+        Ok(lags)
+    }
+
+    /// The checkpoint syncer set for `origin_chain_name` with `set_checkpoint_syncer`, if any
+    fn checkpoint_syncer(&self, origin_chain_name: &str) -> Result<Option<CheckpointSyncerConfig>> {
+        let checkpoint_syncer_path = self.checkpoint_syncer_path(origin_chain_name);
+        if !checkpoint_syncer_path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(checkpoint_syncer_path)?;
+        Ok(Some(serde_json::from_str(&contents)?))
+    }
+
+    /// The keystore-backed validator key for this service
+    fn validator_key(&self) -> Result<PrivateKeySigner> {
+        // A rotated key replaces whatever key the operator config would give
+        if let Some(keys) = RotatedKeys::load(&self.data_dir)? {
+            let keystore = key_rotation::keystore(&self.data_dir)?;
+            return key_rotation::signer(&keystore, &keys.current);
+        }
+
+        let keystore = self.env.keystore();
+        let ecdsa_pub = keystore.first_local::<SpEcdsa>()?;
+        let ecdsa_pair = keystore.get_secret::<SpEcdsa>(&ecdsa_pub)?;
+        let tangle_ecdsa_pair = TanglePairSigner::new(ecdsa_pair.0);
+        let operator_key = tangle_ecdsa_pair.alloy_key()?;
+
+        if self.operator_config.signer == ValidatorSigner::Keystore {
+            return Ok(operator_key);
+        }
+
+        let settings = self.env.protocol_settings.tangle()?;
+        let Some(service_id) = settings.service_id else {
+            return Err(eyre!(
+                "Service ID is unknown, unable to derive the validator key"
+            ));
+        };
+
+        Ok(signer::derive_validator_key(
+            &operator_key.to_bytes(),
+            settings.blueprint_id,
+            service_id,
+        ))
+    }
+
+    /// The address the validators of this service sign checkpoints with
+    pub async fn validator_address(&self) -> Result<Address> {
+        match &self.operator_config.signer {
+            ValidatorSigner::Derived | ValidatorSigner::Keystore => {
+                Ok(self.validator_key()?.address())
+            }
+            ValidatorSigner::AwsKms(kms) => kms.address().await,
+        }
+    }
+
+    /// Recreate the validator of every active chain, e.g. to pick up a new key
+    async fn restart_validators(&self) -> Result<()> {
+        for origin_chain_name in self.active_chains()? {
+            self.remove_existing_container(&origin_chain_name).await?;
+            self.spinup_container(&origin_chain_name).await?;
         }
 
-        self.spinup_container().await?;
         Ok(())
     }
 
-    pub async fn remove_existing_container(&self) -> Result<()> {
-        let mut container_id = self.container.lock().await;
-        if let Some(container_id) = container_id.take() {
-            blueprint_sdk::warn!("Removing existing container...");
+    async fn revert_configs(&self, origin_chain_name: &str) -> Result<()> {
+        blueprint_sdk::error!(
+            "Container for `{origin_chain_name}` failed to start with new configs, reverting"
+        );
+
+        self.remove_existing_container(origin_chain_name).await?;
+
+        let configs_path = self.agent_configs_path(origin_chain_name);
+        if !restore_backup(&configs_path)? {
+            // There is no config to revert, don't leave the broken one around to be picked up later
+            remove_path(&configs_path)?;
+            return Err(eyre!("Configs failed to apply, with no fallback"));
+        }
+
+        self.spinup_container(origin_chain_name).await?;
+        Ok(())
+    }
+
+    /// Restore the backup of a per-chain setting at `path`, and restart the chain's validator
+    ///
+    /// If there's no backup, the setting didn't exist before and is removed, falling back to the
+    /// default.
+    async fn revert_setting(&self, origin_chain_name: &str, path: &Path) -> Result<()> {
+        blueprint_sdk::error!(
+            "Container for `{origin_chain_name}` failed to start with the new `{}`, reverting",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+
+        self.remove_existing_container(origin_chain_name).await?;
+
+        revert(path)?;
+
+        self.spinup_container(origin_chain_name).await?;
+        Ok(())
+    }
+
+    /// The agent image `origin_chain_name` runs, either from an upgrade or the operator's default
+    fn agent_image(&self, origin_chain_name: &str) -> Result<String> {
+        let agent_image = chain_state::agent_image(&self.chain_dir(origin_chain_name))?;
+        Ok(agent_image.unwrap_or_else(|| self.operator_config.agent_image.clone()))
+    }
+
+    /// The agent image the container for `origin_chain_name` is running, falling back to
+    /// [`Self::agent_image`] if there is no container
+    async fn running_agent_image(&self, origin_chain_name: &str) -> Result<String> {
+        let container_id = self.containers.lock().await.get(origin_chain_name).cloned();
+        if let Some(container_id) = container_id {
+            let client = self.connection.client();
+            let image = status::container_state(&client, &container_id)
+                .await
+                .and_then(|state| state.image);
+            if let Some(image) = image {
+                return Ok(image);
+            }
+        }
+
+        self.agent_image(origin_chain_name)
+    }
+
+    pub async fn remove_existing_container(&self, origin_chain_name: &str) -> Result<()> {
+        let mut containers = self.containers.lock().await;
+        if let Some(container_id) = containers.remove(origin_chain_name) {
+            blueprint_sdk::warn!("Removing existing container for `{origin_chain_name}`...");
             let mut c = Container::from_id(self.connection.client(), container_id).await?;
             c.stop().await?;
             c.remove(None).await?;
@@ -199,36 +1126,226 @@ impl HyperlaneContext {
         Ok(())
     }
 
-    fn hyperlane_db_path(&self) -> PathBuf {
-        self.data_dir.join("hyperlane_db")
+    /// Move a data dir from before multiple origin chains were supported into `chains/`
+    async fn migrate_legacy_layout(&self) -> Result<()> {
+        let legacy_origin_chain_name_path = self.data_dir.join("origin_chain_name.txt");
+        if !legacy_origin_chain_name_path.exists() {
+            return Ok(());
+        }
+
+        let origin_chain_name = std::fs::read_to_string(&legacy_origin_chain_name_path)?;
+        let origin_chain_name = origin_chain_name.trim();
+        validate_origin_chain_name(origin_chain_name)?;
+
+        // The old validator would keep running on the moved `hyperlane_db`, next to the new one
+        self.remove_legacy_containers().await?;
+
+        blueprint_sdk::info!("Migrating data dir for `{origin_chain_name}` to `chains/`");
+
+        let chain_dir = self.chain_dir(origin_chain_name);
+        std::fs::create_dir_all(&chain_dir)?;
+
+        for name in ["hyperlane_db", "agent_configs"] {
+            let legacy_path = self.data_dir.join(name);
+            if legacy_path.exists() {
+                std::fs::rename(legacy_path, chain_dir.join(name))?;
+            }
+        }
+
+        let legacy_backup_path = self.data_dir.join("agent_configs.orig");
+        if legacy_backup_path.exists() {
+            std::fs::remove_dir_all(legacy_backup_path)?;
+        }
+
+        let legacy_origin_backup_path = self.data_dir.join("origin_chain_name.txt.orig");
+        if legacy_origin_backup_path.exists() {
+            std::fs::remove_file(legacy_origin_backup_path)?;
+        }
+
+        std::fs::remove_file(legacy_origin_chain_name_path)?;
+        Ok(())
+    }
+
+    /// Remove validator containers started before multiple origin chains were supported
+    ///
+    /// They aren't labelled, so they're found by their image and the legacy `hyperlane_db` they
+    /// mount instead.
+    async fn remove_legacy_containers(&self) -> Result<()> {
+        let legacy_db_path = self.data_dir.join("hyperlane_db");
+        let legacy_db_path = legacy_db_path.to_string_lossy();
+
+        let client = self.connection.client();
+        // The tag may have moved since, so also match containers by the image they were created from
+        let legacy_image_id = client
+            .inspect_image(LEGACY_AGENT_IMAGE)
+            .await
+            .ok()
+            .and_then(|image| image.id);
+        let existing = client
+            .list_containers(Some(ListContainersOptions::<String> {
+                all: true,
+                ..Default::default()
+            }))
+            .await?;
+
+        for summary in existing {
+            let Some(id) = summary.id else {
+                continue;
+            };
+
+            let legacy_image = summary.image.as_deref() == Some(LEGACY_AGENT_IMAGE)
+                || (legacy_image_id.is_some() && summary.image_id == legacy_image_id);
+            let mounts_legacy_db = summary
+                .mounts
+                .iter()
+                .flatten()
+                .any(|mount| mount.source.as_deref() == Some(&*legacy_db_path));
+            if !legacy_image || !mounts_legacy_db {
+                continue;
+            }
+
+            blueprint_sdk::warn!("Removing legacy validator container `{id}`");
+            let mut c = Container::from_id(self.connection.client(), id).await?;
+            c.remove(Some(RemoveContainerOptions {
+                force: true,
+                ..Default::default()
+            }))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    fn chains_dir(&self) -> PathBuf {
+        self.data_dir.join("chains")
+    }
+
+    fn chain_dir(&self, origin_chain_name: &str) -> PathBuf {
+        self.chains_dir().join(origin_chain_name)
+    }
+
+    fn hyperlane_db_path(&self, origin_chain_name: &str) -> PathBuf {
+        self.chain_dir(origin_chain_name)
+            .join(chain_state::HYPERLANE_DB_DIR)
+    }
+
+    fn agent_configs_path(&self, origin_chain_name: &str) -> PathBuf {
+        self.chain_dir(origin_chain_name)
+            .join(chain_state::AGENT_CONFIGS_DIR)
+    }
+
+    fn agent_image_path(&self, origin_chain_name: &str) -> PathBuf {
+        self.chain_dir(origin_chain_name)
+            .join(chain_state::AGENT_IMAGE_FILE)
     }
 
-    fn agent_configs_path(&self) -> PathBuf {
-        self.data_dir.join("agent_configs")
+    fn checkpoint_syncer_path(&self, origin_chain_name: &str) -> PathBuf {
+        self.chain_dir(origin_chain_name)
+            .join("checkpoint_syncer.json")
     }
 
-    fn original_agent_configs_path(&self) -> PathBuf {
-        self.data_dir.join("agent_configs.orig")
+    fn checkpoints_path(&self, origin_chain_name: &str) -> PathBuf {
+        self.chain_dir(origin_chain_name)
+            .join(checkpoint_server::CHECKPOINTS_DIR)
     }
 
-    fn origin_chain_name_path(&self) -> PathBuf {
-        self.data_dir.join("origin_chain_name.txt")
+    fn mirror_progress_path(&self, origin_chain_name: &str) -> PathBuf {
+        self.chain_dir(origin_chain_name)
+            .join("checkpoint_mirrors.json")
     }
 
-    fn original_origin_chain_name_path(&self) -> PathBuf {
-        self.data_dir.join("origin_chain_name.txt.orig")
+    fn checkpoint_audit_path(&self, origin_chain_name: &str) -> PathBuf {
+        self.chain_dir(origin_chain_name)
+            .join("checkpoint_audit.json")
     }
+
+    fn checkpoint_lag_path(&self, origin_chain_name: &str) -> PathBuf {
+        self.chain_dir(origin_chain_name)
+            .join("checkpoint_lag.json")
+    }
+
+    fn secrets_path(&self, origin_chain_name: &str) -> PathBuf {
+        self.chain_dir(origin_chain_name).join("secrets")
+    }
+}
+
+/// Write a file only readable by its owner
+pub(crate) fn write_secret(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(contents)?;
+    Ok(())
+}
+
+/// Origin chain names are used as directory names, so only allow what Hyperlane uses for chain names
+fn validate_origin_chain_name(origin_chain_name: &str) -> Result<()> {
+    if origin_chain_name.is_empty() {
+        return Err(eyre!(
+            "`origin_chain_name` is invalid, ensure it contains a name"
+        ));
+    }
+
+    let valid = origin_chain_name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    if !valid {
+        return Err(eyre!(
+            "`origin_chain_name` `{origin_chain_name}` is invalid, only lowercase letters, digits, `-` and `_` are allowed"
+        ));
+    }
+
+    Ok(())
 }
 
 pub const SET_CONFIG_JOB_ID: u8 = 0;
 
+/// Whether `set_config` managed to apply the new configs
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigStatus {
+    /// The validator is running with the new configs
+    Applied,
+    /// The validator was already running with the same configs, and was left alone
+    Unchanged,
+    /// The validator failed to start with the new configs, and is running with the previous ones
+    Reverted,
+    /// The validator failed to start, and isn't running
+    #[default]
+    Failed,
+}
+
+/// The result of `set_config`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetConfigResult {
+    pub status: ConfigStatus,
+    /// The address the validator signs checkpoints with
+    pub validator_address: String,
+    /// The [`config::config_hash`] of the configs the validator is running with, empty if it isn't
+    pub config_hash: String,
+    /// The agent image the validator is running, empty if it isn't
+    pub agent_image: String,
+}
+
+/// Add or replace the validator for `origin_chain_name`
+///
+/// Validators for other origin chains are left running. Before anything is changed, the validator's
+/// balance on the origin chain is checked, and if it's too low to announce, the job fails with the
+/// address to fund (unless the operator only wants a warning).
+///
+/// If the validator is already running with the same configs, it's left alone. Otherwise, if it
+/// fails to start with the new configs, the previous ones are restored. The result says which
+/// configs the validator ended up running with.
 pub async fn set_config(
     Context(ctx): Context<HyperlaneContext>,
     TangleArgs2(Optional(config_urls), origin_chain_name): TangleArgs2<
         Optional<List<String>>,
         String,
     >,
-) -> Result<TangleResult<u64>> {
+) -> Result<TangleResult<SetConfigResult>> {
     let mut configs = Vec::new();
     if let Some(List(config_urls)) = config_urls {
         for config_url in config_urls {
@@ -243,42 +1360,34 @@ pub async fn set_config(
         }
     }
 
-    // TODO: First step, verify the config is valid. Is there an easy way to do so?
-    if origin_chain_name.is_empty() {
-        return Err(eyre!(
-            "`origin_chain_name` is invalid, ensure it contains a name"
-        ));
-    }
+    validate_origin_chain_name(&origin_chain_name)?;
 
-    ctx.remove_existing_container().await?;
+    // Verify the configs before touching the running validator
+    config::validate_configs(&configs, &origin_chain_name)?;
+    let config = AgentConfig::merged(&configs)?;
 
-    let configs_path = ctx.agent_configs_path();
-    if configs_path.exists() {
-        let orig_configs_path = ctx.original_agent_configs_path();
-        blueprint_sdk::info!("Configs path exists, backing up.");
-        if orig_configs_path.exists() {
-            blueprint_sdk::warn!("Removing old backup at {}", orig_configs_path.display());
-            std::fs::remove_dir_all(&orig_configs_path)?;
-        }
+    // Recreating the container for the same configs only causes downtime and another announcement
+    let config_hash = config::config_hash(&config, &origin_chain_name)?;
+    if ctx.running_config_hash(&origin_chain_name).await? == Some(config_hash) {
+        blueprint_sdk::info!("Configs for `{origin_chain_name}` are unchanged, leaving it running");
+        return Ok(TangleResult(SetConfigResult {
+            status: ConfigStatus::Unchanged,
+            validator_address: ctx.validator_address().await?.to_string(),
+            config_hash: config_hash.to_string(),
+            agent_image: ctx.agent_image(&origin_chain_name)?,
+        }));
+    }
 
-        std::fs::rename(&configs_path, orig_configs_path)?;
-        std::fs::create_dir_all(&configs_path)?;
+    if !configs.is_empty() {
+        let address = ctx.validator_address().await?;
+        ctx.check_validator_balance(&config, &origin_chain_name, address)
+            .await?;
     }
 
-    let origin_chain_name_path = ctx.origin_chain_name_path();
-    if origin_chain_name_path.exists() {
-        let orig_origin_chain_name_path = ctx.original_origin_chain_name_path();
-        if orig_origin_chain_name_path.exists() {
-            blueprint_sdk::warn!(
-                "Removing old backup at {}",
-                orig_origin_chain_name_path.display()
-            );
-            std::fs::remove_file(&orig_origin_chain_name_path)?;
-        }
+    ctx.remove_existing_container(&origin_chain_name).await?;
 
-        blueprint_sdk::info!("Origin chain exists, backing up.");
-        std::fs::rename(&origin_chain_name_path, orig_origin_chain_name_path)?;
-    }
+    let configs_path = ctx.agent_configs_path(&origin_chain_name);
+    backup(&configs_path)?;
 
     std::fs::create_dir_all(&configs_path)?;
     if configs.is_empty() {
@@ -291,17 +1400,489 @@ pub async fn set_config(
         blueprint_sdk::info!("New configs written to: {}", configs_path.display());
     }
 
-    std::fs::write(&origin_chain_name_path, origin_chain_name)?;
+    // Applying configs starts a stopped or paused validator again
+    chain_state::clear_markers(&ctx.chain_dir(&origin_chain_name))?;
+
+    let status = match ctx.spinup_container(&origin_chain_name).await {
+        Ok(()) => ConfigStatus::Applied,
+        Err(e) => {
+            // Something went wrong spinning up the container, possibly bad config. Try to revert.
+            blueprint_sdk::error!("{e}");
+            match ctx.revert_configs(&origin_chain_name).await {
+                Ok(()) => ConfigStatus::Reverted,
+                Err(e) => {
+                    blueprint_sdk::error!("{e}");
+                    ConfigStatus::Failed
+                }
+            }
+        }
+    };
+
+    let mut result = SetConfigResult {
+        status,
+        validator_address: ctx.validator_address().await?.to_string(),
+        ..Default::default()
+    };
+    if status != ConfigStatus::Failed {
+        let config = ctx.applied_config(&origin_chain_name)?;
+        result.config_hash = config::config_hash(&config, &origin_chain_name)?.to_string();
+        result.agent_image = ctx.agent_image(&origin_chain_name)?;
+    }
+
+    Ok(TangleResult(result))
+}
+
+pub const REMOVE_CHAIN_JOB_ID: u8 = 1;
+
+/// Stop validating `origin_chain_name`
+///
+/// The container and configs are removed, but the chain's `hyperlane_db` is kept so the validator
+/// can pick up where it left off if the chain is added again.
+pub async fn remove_chain(
+    Context(ctx): Context<HyperlaneContext>,
+    TangleArg(origin_chain_name): TangleArg<String>,
+) -> Result<TangleResult<u64>> {
+    validate_origin_chain_name(&origin_chain_name)?;
+
+    let configs_path = ctx.agent_configs_path(&origin_chain_name);
+    if !configs_path.exists() {
+        return Err(eyre!(
+            "No validator is configured for `{origin_chain_name}`"
+        ));
+    }
+
+    ctx.remove_existing_container(&origin_chain_name).await?;
+
+    let agent_image_path = ctx.agent_image_path(&origin_chain_name);
+    let checkpoint_syncer_path = ctx.checkpoint_syncer_path(&origin_chain_name);
+    for path in [configs_path, agent_image_path, checkpoint_syncer_path] {
+        remove_path(&backup_path(&path))?;
+        remove_path(&path)?;
+    }
+
+    remove_path(&ctx.secrets_path(&origin_chain_name))?;
+    chain_state::clear_markers(&ctx.chain_dir(&origin_chain_name))?;
+
+    blueprint_sdk::info!("Removed validator for `{origin_chain_name}`");
+    Ok(TangleResult(0))
+}
+
+pub const UPGRADE_AGENT_JOB_ID: u8 = 2;
+
+/// Move the validator for `origin_chain_name` to an agent version from the operator's allowlist
+///
+/// The container is recreated with the same configs and `hyperlane_db`. If the new version fails
+/// to become ready, the validator is rolled back to its previous image. A stopped validator isn't
+/// started, the new image is used once it is. A paused validator can't be upgraded, since resuming
+/// it has to bring back the same image.
+pub async fn upgrade_agent(
+    Context(ctx): Context<HyperlaneContext>,
+    TangleArgs2(origin_chain_name, version): TangleArgs2<String, String>,
+) -> Result<TangleResult<u64>> {
+    validate_origin_chain_name(&origin_chain_name)?;
+
+    if !ctx.agent_configs_path(&origin_chain_name).exists() {
+        return Err(eyre!(
+            "No validator is configured for `{origin_chain_name}`"
+        ));
+    }
+
+    if ctx.is_paused(&origin_chain_name) {
+        return Err(eyre!(
+            "The validator for `{origin_chain_name}` is paused, resume it before upgrading"
+        ));
+    }
+
+    let Some(new_image) = ctx.operator_config.agent_versions.get(&version) else {
+        let available = ctx
+            .operator_config
+            .agent_versions
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        return Err(eyre!(
+            "Agent version `{version}` is not allowed by the operator, available versions: {available:?}"
+        ));
+    };
+
+    if ctx.agent_image(&origin_chain_name)? == *new_image {
+        blueprint_sdk::info!("`{origin_chain_name}` is already running `{version}`");
+        return Ok(TangleResult(0));
+    }
+
+    // Pull ahead of time, so an unavailable image doesn't cause any downtime
+    image::ensure_image(
+        &ctx.connection.client(),
+        new_image,
+        ctx.operator_config.registry_credentials(),
+    )
+    .await?;
+
+    let stopped = ctx.is_stopped(&origin_chain_name);
+    if !stopped {
+        ctx.remove_existing_container(&origin_chain_name).await?;
+    }
+
+    let agent_image_path = ctx.agent_image_path(&origin_chain_name);
+    backup(&agent_image_path)?;
+    std::fs::write(&agent_image_path, new_image)?;
+    blueprint_sdk::info!("Upgrading `{origin_chain_name}` to `{version}` ({new_image})");
+
+    if stopped {
+        // Picked up once the validator is started again with `set_config`
+        return Ok(TangleResult(0));
+    }
+
+    if let Err(e) = ctx.spinup_container(&origin_chain_name).await {
+        blueprint_sdk::error!("{e}");
+        ctx.revert_setting(&origin_chain_name, &agent_image_path)
+            .await?;
+        return Err(eyre!(
+            "Upgrade to `{version}` failed, rolled back to the previous image: {e}"
+        ));
+    }
+
+    Ok(TangleResult(0))
+}
+
+pub const VALIDATOR_ADDRESS_JOB_ID: u8 = 3;
+
+/// Get the address this service's validators sign checkpoints with
+///
+/// Every origin chain is validated with the same address. It's unique to the service, so it has
+/// to be funded for announcing and, if used, allowlisted in ISMs.
+pub async fn validator_address(
+    Context(ctx): Context<HyperlaneContext>,
+) -> Result<TangleResult<String>> {
+    let address = ctx.validator_address().await?;
+    Ok(TangleResult(address.to_string()))
+}
+
+pub const ROTATE_VALIDATOR_KEY_JOB_ID: u8 = 4;
+
+/// Generate a new validator key for the service
+///
+/// Returns the current and new validator addresses. Validators keep signing with the current key
+/// until the new one is funded and [`switch_validator_key`] is called. Only one rotation can be in
+/// progress at a time.
+pub async fn rotate_validator_key(
+    Context(ctx): Context<HyperlaneContext>,
+) -> Result<TangleResult<Vec<String>>> {
+    reject_kms_rotation(&ctx)?;
+
+    let current_address = ctx.validator_key()?.address();
+    let new_key = key_rotation::rotate(&ctx.data_dir)?;
+    let keystore = key_rotation::keystore(&ctx.data_dir)?;
+    let new_address = key_rotation::signer(&keystore, &new_key)?.address();
     blueprint_sdk::info!(
-        "Origin chain written to: {}",
-        origin_chain_name_path.display()
+        "Generated validator key `{new_address}`, fund it before switching from `{current_address}`"
     );
 
-    if let Err(e) = ctx.spinup_container().await {
-        // Something went wrong spinning up the container, possibly bad config. Try to revert.
+    Ok(TangleResult(vec![
+        current_address.to_string(),
+        new_address.to_string(),
+    ]))
+}
+
+fn reject_kms_rotation(ctx: &HyperlaneContext) -> Result<()> {
+    if let ValidatorSigner::AwsKms(kms) = &ctx.operator_config.signer {
+        return Err(eyre!(
+            "The validator signs with KMS key `{}`, rotate it through KMS instead",
+            kms.key_id
+        ));
+    }
+
+    Ok(())
+}
+
+pub const CONFIRM_KEY_ROTATION_JOB_ID: u8 = 5;
+
+/// Confirm the switch to the rotated key, deleting the previous key
+///
+/// Returns the address the validators now sign with.
+pub async fn confirm_key_rotation(
+    Context(ctx): Context<HyperlaneContext>,
+) -> Result<TangleResult<String>> {
+    let key = key_rotation::confirm(&ctx.data_dir)?;
+
+    let keystore = key_rotation::keystore(&ctx.data_dir)?;
+    let address = key_rotation::signer(&keystore, &key)?.address();
+    blueprint_sdk::info!("Confirmed rotation to validator key `{address}`");
+    Ok(TangleResult(address.to_string()))
+}
+
+pub const SET_CHECKPOINT_SYNCER_JOB_ID: u8 = 6;
+
+/// Set where the validator for `origin_chain_name` publishes its signed checkpoints
+///
+/// `settings` uses the format of `checkpointSyncer` in the agent config, and takes priority over
+/// any checkpoint syncer from `set_config`. Credentials for the storage come from the operator. If
+/// the validator is running, it's restarted with the new settings, and reverted if it fails to
+/// start. A paused validator's checkpoint syncer can't be changed.
+pub async fn set_checkpoint_syncer(
+    Context(ctx): Context<HyperlaneContext>,
+    TangleArgs2(origin_chain_name, settings): TangleArgs2<String, String>,
+) -> Result<TangleResult<u64>> {
+    validate_origin_chain_name(&origin_chain_name)?;
+    let checkpoint_syncer = checkpoint_syncer::parse(&settings)?;
+
+    if ctx.is_paused(&origin_chain_name) {
+        return Err(eyre!(
+            "The validator for `{origin_chain_name}` is paused, resume it before changing its checkpoint syncer"
+        ));
+    }
+
+    let running =
+        ctx.agent_configs_path(&origin_chain_name).exists() && !ctx.is_stopped(&origin_chain_name);
+    if running {
+        ctx.remove_existing_container(&origin_chain_name).await?;
+    }
+
+    std::fs::create_dir_all(ctx.chain_dir(&origin_chain_name))?;
+    let checkpoint_syncer_path = ctx.checkpoint_syncer_path(&origin_chain_name);
+    backup(&checkpoint_syncer_path)?;
+    std::fs::write(
+        &checkpoint_syncer_path,
+        serde_json::to_vec_pretty(&checkpoint_syncer)?,
+    )?;
+    blueprint_sdk::info!(
+        "Checkpoint syncer for `{origin_chain_name}` set to {checkpoint_syncer:?}"
+    );
+
+    if !running {
+        // Picked up once the chain is added or started again with `set_config`
+        return Ok(TangleResult(0));
+    }
+
+    if let Err(e) = ctx.spinup_container(&origin_chain_name).await {
+        blueprint_sdk::error!("{e}");
+        ctx.revert_setting(&origin_chain_name, &checkpoint_syncer_path)
+            .await?;
+        return Err(eyre!(
+            "Validator failed to start with the new checkpoint syncer, reverted: {e}"
+        ));
+    }
+
+    Ok(TangleResult(0))
+}
+
+pub const CHECKPOINT_AUDIT_JOB_ID: u8 = 7;
+
+/// Get the latest audit of every validator's signed checkpoints
+///
+/// Returns a JSON object keyed by origin chain. Validators whose checkpoints the blueprint can't
+/// read, such as those publishing to GCS, are never audited.
+pub async fn checkpoint_audit(
+    Context(ctx): Context<HyperlaneContext>,
+) -> Result<TangleResult<String>> {
+    let audits = ctx.checkpoint_audits()?;
+    Ok(TangleResult(serde_json::to_string(&audits)?))
+}
+
+pub const CHECKPOINT_LAG_JOB_ID: u8 = 8;
+
+/// Get how far every validator's signed checkpoints are behind its origin chain
+///
+/// Returns a JSON object keyed by origin chain, with the last measurement for each. Validators
+/// whose checkpoints the blueprint can't read, such as those publishing to GCS, are never measured.
+pub async fn checkpoint_lag(
+    Context(ctx): Context<HyperlaneContext>,
+) -> Result<TangleResult<String>> {
+    let lags = ctx.checkpoint_lags()?;
+    Ok(TangleResult(serde_json::to_string(&lags)?))
+}
+
+pub const ISM_MEMBERSHIP_JOB_ID: u8 = 9;
+
+/// Check whether the validator for `origin_chain_name` is trusted on each destination chain
+///
+/// Every other chain in the applied configs counts as a destination. The default ISM of each
+/// destination's mailbox is resolved, through routing and aggregation ISMs, to the multisig ISMs
+/// verifying messages from the origin chain. Returns a JSON object keyed by destination chain.
+pub async fn ism_membership(
+    Context(ctx): Context<HyperlaneContext>,
+    TangleArg(origin_chain_name): TangleArg<String>,
+) -> Result<TangleResult<String>> {
+    validate_origin_chain_name(&origin_chain_name)?;
+    if !ctx.agent_configs_path(&origin_chain_name).exists() {
+        return Err(eyre!("`{origin_chain_name}` isn't configured"));
+    }
+
+    let memberships = ctx.ism_membership(&origin_chain_name).await?;
+    Ok(TangleResult(serde_json::to_string(&memberships)?))
+}
+
+pub const VALIDATOR_STATUS_JOB_ID: u8 = 10;
+
+/// Get what the operator is running for every configured origin chain
+///
+/// Returns a JSON object keyed by origin chain, with the config hash, container state, uptime,
+/// image, validator address and latest checkpoint index of each. Nothing is changed.
+pub async fn validator_status(
+    Context(ctx): Context<HyperlaneContext>,
+) -> Result<TangleResult<String>> {
+    // The same for every chain, and with KMS the key may be unreachable
+    let validator_address = match ctx.validator_address().await {
+        Ok(address) => address.to_string(),
+        Err(e) => {
+            blueprint_sdk::warn!("Unable to get the validator address: {e}");
+            String::from(status::ADDRESS_UNAVAILABLE)
+        }
+    };
+
+    let mut statuses = BTreeMap::new();
+    for origin_chain_name in ctx.configured_chains()? {
+        let status = ctx
+            .validator_status(&origin_chain_name, &validator_address)
+            .await?;
+        statuses.insert(origin_chain_name, status);
+    }
+
+    Ok(TangleResult(serde_json::to_string(&statuses)?))
+}
+
+pub const STOP_VALIDATOR_JOB_ID: u8 = 11;
+
+/// Stop the validator for `origin_chain_name`, and keep it stopped
+///
+/// The container is removed, but the configs are kept, and the chain stays stopped across
+/// blueprint restarts until `set_config` is called for it again. `db` chooses what happens to the
+/// chain's `hyperlane_db`: `keep` (the default), `archive` to move it aside, or `delete`.
+pub async fn stop_validator(
+    Context(ctx): Context<HyperlaneContext>,
+    TangleArgs2(origin_chain_name, Optional(db)): TangleArgs2<String, Optional<String>>,
+) -> Result<TangleResult<u64>> {
+    validate_origin_chain_name(&origin_chain_name)?;
+
+    let db = match db {
+        Some(db) => db.parse::<DbAction>()?,
+        None => DbAction::Keep,
+    };
+
+    if !ctx.agent_configs_path(&origin_chain_name).exists() {
+        return Err(eyre!(
+            "No validator is configured for `{origin_chain_name}`"
+        ));
+    }
+
+    // Mark the chain first, so it isn't restarted if anything below fails
+    let chain_dir = ctx.chain_dir(&origin_chain_name);
+    chain_state::mark_stopped(&chain_dir)?;
+    ctx.remove_existing_container(&origin_chain_name).await?;
+
+    if let Some(archive_path) = chain_state::apply_db_action(&chain_dir, db)? {
+        blueprint_sdk::info!(
+            "Archived `hyperlane_db` of `{origin_chain_name}` to `{}`",
+            archive_path.display()
+        );
+    } else if db == DbAction::Delete {
+        blueprint_sdk::info!("Deleted `hyperlane_db` of `{origin_chain_name}`");
+    }
+
+    blueprint_sdk::info!("Stopped validator for `{origin_chain_name}`");
+    Ok(TangleResult(0))
+}
+
+pub const PAUSE_VALIDATOR_JOB_ID: u8 = 12;
+
+/// Temporarily stop the validator for `origin_chain_name`, e.g. while its origin chain is halted
+///
+/// Only the container is removed, the configs and `hyperlane_db` are left untouched, and the image
+/// it was running is recorded. The validator stays paused across blueprint restarts, until
+/// [`resume_validator`] is called.
+pub async fn pause_validator(
+    Context(ctx): Context<HyperlaneContext>,
+    TangleArg(origin_chain_name): TangleArg<String>,
+) -> Result<TangleResult<u64>> {
+    validate_origin_chain_name(&origin_chain_name)?;
+
+    let agent_image = ctx.running_agent_image(&origin_chain_name).await?;
+    chain_state::mark_paused(&ctx.chain_dir(&origin_chain_name), &agent_image)
+        .map_err(|e| eyre!("Unable to pause the validator for `{origin_chain_name}`: {e}"))?;
+    ctx.remove_existing_container(&origin_chain_name).await?;
+
+    blueprint_sdk::info!("Paused validator for `{origin_chain_name}`");
+    Ok(TangleResult(0))
+}
+
+pub const RESUME_VALIDATOR_JOB_ID: u8 = 13;
+
+/// Start the validator for `origin_chain_name` again after [`pause_validator`]
+///
+/// The validator comes back with the same configs, image and `hyperlane_db` it was paused with. If
+/// it fails to start, it stays paused.
+pub async fn resume_validator(
+    Context(ctx): Context<HyperlaneContext>,
+    TangleArg(origin_chain_name): TangleArg<String>,
+) -> Result<TangleResult<u64>> {
+    validate_origin_chain_name(&origin_chain_name)?;
+
+    if !ctx.is_paused(&origin_chain_name) {
+        return Err(eyre!(
+            "The validator for `{origin_chain_name}` isn't paused"
+        ));
+    }
+
+    if let Err(e) = ctx.spinup_container(&origin_chain_name).await {
         blueprint_sdk::error!("{e}");
-        ctx.revert_configs().await?;
+        ctx.remove_existing_container(&origin_chain_name).await?;
+        return Err(eyre!(
+            "The validator for `{origin_chain_name}` failed to resume, it's still paused: {e}"
+        ));
     }
 
+    chain_state::clear_paused(&ctx.chain_dir(&origin_chain_name))?;
+
+    blueprint_sdk::info!("Resumed validator for `{origin_chain_name}`");
     Ok(TangleResult(0))
 }
+
+pub const SWITCH_VALIDATOR_KEY_JOB_ID: u8 = 14;
+
+/// Switch every validator of the service to the key generated by [`rotate_validator_key`]
+///
+/// The new key has to pass the same balance check as [`set_config`] on every active chain first.
+/// Returns the old and new validator addresses. If any validator fails to start with the new key,
+/// all of them are rolled back to the old one, and the new key stays pending. The old key is kept
+/// until the switch is confirmed with [`confirm_key_rotation`], which should happen once the new
+/// address is in the destination chains' ISMs.
+pub async fn switch_validator_key(
+    Context(ctx): Context<HyperlaneContext>,
+) -> Result<TangleResult<Vec<String>>> {
+    reject_kms_rotation(&ctx)?;
+
+    let Some(new_key) = key_rotation::pending(&ctx.data_dir)? else {
+        return Err(eyre!(
+            "No validator key is waiting to be switched to, rotate the key first"
+        ));
+    };
+    let keystore = key_rotation::keystore(&ctx.data_dir)?;
+    let new_address = key_rotation::signer(&keystore, &new_key)?.address();
+
+    // An unfunded key can't announce, so its validators would never become ready
+    for origin_chain_name in ctx.active_chains()? {
+        let config = ctx.applied_config(&origin_chain_name)?;
+        ctx.check_validator_balance(&config, &origin_chain_name, new_address)
+            .await?;
+    }
+
+    let old_address = ctx.validator_key()?.address();
+    let previous_keys = key_rotation::switch(&ctx.data_dir)?;
+    blueprint_sdk::info!("Switching validator key from `{old_address}` to `{new_address}`");
+
+    if let Err(e) = ctx.restart_validators().await {
+        blueprint_sdk::error!("{e}");
+
+        key_rotation::rollback(&ctx.data_dir, previous_keys)?;
+        ctx.restart_validators().await?;
+
+        return Err(eyre!(
+            "Switching the validator key failed, rolled back to `{old_address}`: {e}"
+        ));
+    }
+
+    Ok(TangleResult(vec![
+        old_address.to_string(),
+        new_address.to_string(),
+    ]))
+}
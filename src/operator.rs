@@ -0,0 +1,154 @@
+//! Operator-side settings for the blueprint
+//!
+//! These are read from `operator.json` in the blueprint's data directory. Every field is optional,
+//! a missing file or field falls back to the defaults.
+
+use crate::checkpoint_mirror::CheckpointMirror;
+use crate::checkpoint_syncer::CheckpointStorageAccess;
+use crate::image;
+use crate::signer::ValidatorSigner;
+use blueprint_sdk::alloy::primitives::U256;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use docktopus::bollard::auth::DockerCredentials;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// The agent image used when the operator doesn't specify one
+///
+/// This is the only image run without a digest.
+// TODO: Pin by digest (`<repo>@sha256:<digest>`)
+pub const DEFAULT_AGENT_IMAGE: &str = "gcr.io/abacus-labs-dev/hyperlane-agent:agents-v1.2.0";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OperatorConfig {
+    /// The Hyperlane agent image to run, pinned by digest (`repo@sha256:...`)
+    pub agent_image: String,
+    /// Agent versions service owners can upgrade to, mapping a version name to its image
+    pub agent_versions: BTreeMap<String, String>,
+    /// How long a new validator has to show it's running before it's considered failed, in seconds
+    pub readiness_timeout_secs: u64,
+    /// How the validator signs checkpoints
+    pub signer: ValidatorSigner,
+    /// Credentials for the storage validators publish checkpoints to
+    pub checkpoint_storage: CheckpointStorageAccess,
+    /// Serve the checkpoints of validators using `localStorage` over HTTP
+    pub serve_checkpoints: bool,
+    /// Extra storage every validator's checkpoints are copied to
+    pub checkpoint_mirrors: Vec<CheckpointMirror>,
+    /// How many messages a validator can be behind on signing before it's reported as behind
+    pub max_checkpoint_lag: u32,
+    /// The least native balance the validator needs on its origin chain to announce itself, in wei
+    pub min_validator_balance: U256,
+    /// What `set_config` does when the validator's balance is below `min_validator_balance`
+    pub low_balance: LowBalanceAction,
+    /// Log whether the destination chains' ISMs trust the validator whenever it starts
+    pub check_ism_membership: bool,
+    /// Credentials for the registry hosting the agent image, if it's private
+    pub registry_auth: Option<RegistryAuth>,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LowBalanceAction {
+    /// Refuse to start the validator
+    #[default]
+    Fail,
+    /// Start the validator anyway, logging a warning
+    Warn,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryAuth {
+    pub username: String,
+    pub password: String,
+    /// The registry to authenticate with, e.g. `gcr.io`
+    pub server_address: Option<String>,
+}
+
+impl core::fmt::Debug for RegistryAuth {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RegistryAuth")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .field("server_address", &self.server_address)
+            .finish()
+    }
+}
+
+impl Default for OperatorConfig {
+    fn default() -> Self {
+        Self {
+            agent_image: String::from(DEFAULT_AGENT_IMAGE),
+            agent_versions: BTreeMap::new(),
+            readiness_timeout_secs: 120,
+            signer: ValidatorSigner::Keystore,
+            checkpoint_storage: CheckpointStorageAccess::default(),
+            serve_checkpoints: false,
+            checkpoint_mirrors: Vec::new(),
+            max_checkpoint_lag: 20,
+            // Anything at all, an empty account can never announce
+            min_validator_balance: U256::from(1),
+            low_balance: LowBalanceAction::Fail,
+            check_ism_membership: false,
+            registry_auth: None,
+        }
+    }
+}
+
+impl OperatorConfig {
+    pub const FILE_NAME: &str = "operator.json";
+
+    /// Load the config at `path`, falling back to the defaults if it doesn't exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        let config: Self = serde_json::from_str(&contents)
+            .map_err(|e| eyre!("Invalid operator config at `{}`: {e}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check the settings serde can't
+    fn validate(&self) -> Result<()> {
+        if self.agent_image != DEFAULT_AGENT_IMAGE
+            && image::pinned_digest(&self.agent_image).is_none()
+        {
+            return Err(eyre!(
+                "`agent_image` must be pinned by digest (`<repo>@sha256:<digest>`), got `{}`",
+                self.agent_image
+            ));
+        }
+
+        // Service owners pick from these, so they must get exactly the image the operator vetted
+        for (version, image) in &self.agent_versions {
+            if image::pinned_digest(image).is_none() {
+                return Err(eyre!(
+                    "Agent version `{version}` must be pinned by digest (`<repo>@sha256:<digest>`), got `{image}`"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn readiness_timeout(&self) -> Duration {
+        Duration::from_secs(self.readiness_timeout_secs)
+    }
+
+    pub(crate) fn registry_credentials(&self) -> Option<DockerCredentials> {
+        let auth = self.registry_auth.as_ref()?;
+        Some(DockerCredentials {
+            username: Some(auth.username.clone()),
+            password: Some(auth.password.clone()),
+            serveraddress: auth.server_address.clone(),
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,187 @@
+//! Readiness checks for a freshly started validator container
+//!
+//! The validator is considered ready once its logs show that it has announced its checkpoint
+//! storage location, or that it's signing checkpoints. Until then, anything that would keep it
+//! from doing so (the container exiting, the signer lacking funds, ...) fails the check.
+//!
+//! Once the logs say so, the announcement is also checked on the origin chain, since relayers
+//! can't find the validator's signatures otherwise.
+
+use crate::announcement;
+use blueprint_sdk as sdk;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use docktopus::bollard::Docker;
+use docktopus::bollard::container::LogsOptions;
+use futures::StreamExt;
+use sdk::alloy::primitives::Address;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Log lines showing the validator is up and doing its job
+const READY_MARKERS: &[&str] = &[
+    "Validator has announced signature storage location",
+    "Signed all queued checkpoints",
+];
+
+/// Log lines showing the validator is stuck, and won't become ready on its own
+const FAILURE_MARKERS: &[(&str, &str)] = &[
+    (
+        "Please send tokens to your chain signer address",
+        "the validator needs funds on the origin chain to announce itself",
+    ),
+    (
+        "Transaction attempting to announce validator reverted",
+        "the announce transaction reverted",
+    ),
+];
+
+/// The number of log lines to include in failure reports
+pub const RECENT_LINES: usize = 10;
+
+/// How often `ValidatorAnnounce` is queried while waiting for the announcement to show up
+const ANNOUNCEMENT_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// What a validator's logs show about its readiness
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Readiness {
+    /// Nothing conclusive yet
+    Pending,
+    Ready,
+    /// The validator is stuck, for the given reason
+    Failed(String),
+}
+
+/// Matches a validator's log lines against the readiness and failure markers, keeping the most
+/// recent ones for failure reports
+#[derive(Debug, Default)]
+pub struct LogWatcher {
+    recent: VecDeque<String>,
+}
+
+impl LogWatcher {
+    /// Record the next log line, returning what the logs show so far
+    pub fn push(&mut self, line: &str) -> Readiness {
+        if self.recent.len() == RECENT_LINES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(line.to_string());
+
+        if READY_MARKERS.iter().any(|marker| line.contains(marker)) {
+            return Readiness::Ready;
+        }
+
+        match FAILURE_MARKERS
+            .iter()
+            .find(|(marker, _)| line.contains(marker))
+        {
+            Some((_, reason)) => Readiness::Failed((*reason).to_string()),
+            None => Readiness::Pending,
+        }
+    }
+
+    /// The last [`RECENT_LINES`] log lines
+    pub fn recent(&self) -> Vec<String> {
+        Vec::from(self.recent.clone())
+    }
+
+    /// The error reported when the validator isn't ready, for `reason`
+    pub fn failure(&self, reason: &str) -> color_eyre::Report {
+        let recent = self.recent().join("\n");
+        eyre!("Validator failed to become ready: {reason}\nRecent logs:\n{recent}")
+    }
+}
+
+/// Wait until the validator in `container_id` is ready, or `timeout` expires
+pub(crate) async fn wait_for_validator(
+    docker: &Docker,
+    container_id: &str,
+    timeout: Duration,
+) -> Result<()> {
+    let mut watcher = LogWatcher::default();
+    let result =
+        tokio::time::timeout(timeout, watch_logs(docker, container_id, &mut watcher)).await;
+
+    let reason = match result {
+        Ok(Ok(())) => return Ok(()),
+        Ok(Err(reason)) => reason,
+        Err(_) => format!(
+            "no sign of the validator running within {}s",
+            timeout.as_secs()
+        ),
+    };
+
+    Err(watcher.failure(&reason))
+}
+
+/// Wait until `validator` has announced `expected_location` on the `ValidatorAnnounce` at
+/// `validator_announce`, or `timeout` expires
+pub(crate) async fn wait_for_announcement(
+    rpc_url: &str,
+    validator_announce: Address,
+    validator: Address,
+    expected_location: &str,
+    timeout: Duration,
+) -> Result<()> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        // The announcement may not be visible to our RPC yet, so only the last answer counts
+        let announced =
+            announcement::announced_locations(rpc_url, validator_announce, validator).await;
+        let checked = announced
+            .map_err(|e| e.to_string())
+            .and_then(|announced| announcement::check_announced(expected_location, &announced));
+        let reason = match checked {
+            Ok(()) => return Ok(()),
+            Err(reason) => reason,
+        };
+
+        if tokio::time::Instant::now() + ANNOUNCEMENT_POLL_INTERVAL > deadline {
+            return Err(eyre!("Validator failed to become ready: {reason}"));
+        }
+
+        tokio::time::sleep(ANNOUNCEMENT_POLL_INTERVAL).await;
+    }
+}
+
+/// Follow the container's logs until a readiness or failure marker shows up
+///
+/// The returned error is the reason the validator isn't ready.
+async fn watch_logs(
+    docker: &Docker,
+    container_id: &str,
+    watcher: &mut LogWatcher,
+) -> std::result::Result<(), String> {
+    let mut logs = docker.logs(
+        container_id,
+        Some(LogsOptions {
+            follow: true,
+            stdout: true,
+            stderr: true,
+            tail: String::from("all"),
+            ..Default::default()
+        }),
+    );
+
+    while let Some(output) = logs.next().await {
+        let output = output.map_err(|e| format!("unable to read container logs: {e}"))?;
+        for line in output.to_string().lines() {
+            match watcher.push(line) {
+                Readiness::Pending => {}
+                Readiness::Ready => return Ok(()),
+                Readiness::Failed(reason) => return Err(reason),
+            }
+        }
+    }
+
+    // The log stream only ends once the container stops
+    let state = docker
+        .inspect_container(container_id, None)
+        .await
+        .ok()
+        .and_then(|inspect| inspect.state);
+    match state.and_then(|state| state.exit_code) {
+        Some(code) => Err(format!("the container exited with code {code}")),
+        None => Err(String::from("the container stopped")),
+    }
+}
@@ -0,0 +1,168 @@
+//! How the validator signs its checkpoints
+
+use crate::chain_state::remove_path;
+use crate::config::{AgentConfig, SignerConfig};
+use alloy_signer_aws::AwsSigner;
+use blueprint_sdk as sdk;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use sdk::alloy::primitives::{Address, B256, keccak256};
+use sdk::alloy::signers::Signer;
+use sdk::alloy::signers::local::PrivateKeySigner;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The file in a chain's secrets directory holding the validator's signer config
+pub const VALIDATOR_SECRET_FILE: &str = "validator.json";
+
+/// The signer the validator uses, configured by the operator
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ValidatorSigner {
+    /// A key derived from the operator's ECDSA key in the keystore, unique to the service
+    ///
+    /// Opt-in, since switching an existing validator over changes its address.
+    Derived,
+    /// The operator's ECDSA key from the keystore, shared with the operator's Tangle identity
+    #[default]
+    Keystore,
+    /// A secp256k1 key held in AWS KMS
+    AwsKms(AwsKmsSigner),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AwsKmsSigner {
+    /// The KMS key ID or alias (`alias/...`)
+    pub key_id: String,
+    pub region: String,
+    /// Overrides the KMS endpoint of both the blueprint and the agent, only useful for testing
+    /// against a local KMS. It has to be reachable from the agent's container.
+    #[serde(default)]
+    pub endpoint_url: Option<String>,
+    /// Static credentials for the key. If not set, the default AWS credential chain is used.
+    #[serde(default)]
+    pub credentials: Option<AwsCredentials>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+impl core::fmt::Debug for AwsCredentials {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl AwsCredentials {
+    /// An AWS shared credentials file holding these credentials as the default profile
+    pub(crate) fn shared_credentials_file(&self) -> String {
+        format!(
+            "[default]\naws_access_key_id = {}\naws_secret_access_key = {}\n",
+            self.access_key_id, self.secret_access_key
+        )
+    }
+}
+
+impl AwsKmsSigner {
+    /// Connect to KMS and load the key
+    pub async fn connect(&self) -> Result<AwsSigner> {
+        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new(self.region.clone()));
+
+        if let Some(endpoint_url) = &self.endpoint_url {
+            loader = loader.endpoint_url(endpoint_url);
+        }
+
+        if let Some(credentials) = &self.credentials {
+            loader = loader.credentials_provider(aws_sdk_kms::config::Credentials::new(
+                credentials.access_key_id.clone(),
+                credentials.secret_access_key.clone(),
+                None,
+                None,
+                "operator-config",
+            ));
+        }
+
+        let client = aws_sdk_kms::Client::new(&loader.load().await);
+        AwsSigner::new(client, self.key_id.clone(), None)
+            .await
+            .map_err(|e| eyre!("Unable to load KMS key `{}`: {e}", self.key_id))
+    }
+
+    /// The address the validator signs as
+    pub async fn address(&self) -> Result<Address> {
+        Ok(self.connect().await?.address())
+    }
+
+    /// The agent environment selecting this key as the validator's signer
+    pub fn agent_env(&self) -> Vec<String> {
+        let mut env = vec![
+            String::from("HYP_VALIDATOR_TYPE=aws"),
+            format!("HYP_VALIDATOR_ID={}", self.key_id),
+            format!("HYP_VALIDATOR_REGION={}", self.region),
+        ];
+
+        // The agent has to sign with the same KMS the blueprint checked the key against
+        if let Some(endpoint_url) = &self.endpoint_url {
+            env.push(format!("AWS_ENDPOINT_URL_KMS={endpoint_url}"));
+        }
+
+        env
+    }
+}
+
+/// Write `key` into `secrets_path`, as an agent config selecting it as the validator's signer
+///
+/// Returns the config files the agent needs, as mounted in its container.
+pub fn write_key_secret(key: &PrivateKeySigner, secrets_path: &Path) -> Result<Vec<String>> {
+    let signer_config = AgentConfig {
+        validator: Some(SignerConfig::HexKey {
+            key: format!("0x{}", hex::encode(key.to_bytes())),
+        }),
+        ..Default::default()
+    };
+    crate::write_secret(
+        &secrets_path.join(VALIDATOR_SECRET_FILE),
+        &serde_json::to_vec(&signer_config)?,
+    )?;
+
+    Ok(vec![format!("/secrets/{VALIDATOR_SECRET_FILE}")])
+}
+
+/// Remove a key written by [`write_key_secret`] from `secrets_path`, if there is one
+pub fn remove_key_secret(secrets_path: &Path) -> Result<()> {
+    remove_path(&secrets_path.join(VALIDATOR_SECRET_FILE))
+}
+
+/// Derive the validator key for a service from the operator's ECDSA key
+///
+/// The same operator key and service always give the same validator key, so it never has to be
+/// stored. Keys for different services can't be linked to each other, or to the operator key.
+pub fn derive_validator_key(
+    operator_key: &B256,
+    blueprint_id: u64,
+    service_id: u64,
+) -> PrivateKeySigner {
+    const DOMAIN: &[u8] = b"hyperlane-validator-blueprint/validator-key";
+
+    let mut seed = Vec::with_capacity(DOMAIN.len() + 32 + 8 + 8);
+    seed.extend_from_slice(DOMAIN);
+    seed.extend_from_slice(operator_key.as_slice());
+    seed.extend_from_slice(&blueprint_id.to_be_bytes());
+    seed.extend_from_slice(&service_id.to_be_bytes());
+
+    // A hash that isn't a valid secp256k1 scalar is astronomically unlikely, rehash if it happens
+    let mut key = keccak256(&seed);
+    loop {
+        match PrivateKeySigner::from_bytes(&key) {
+            Ok(signer) => return signer,
+            Err(_) => key = keccak256(key),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! What the operator is running for each origin chain
+
+use chrono::{DateTime, Utc};
+use docktopus::bollard::Docker;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Reported as the validator address when it can't be looked up, e.g. while KMS is unreachable
+pub const ADDRESS_UNAVAILABLE: &str = "unavailable";
+
+/// The state of the validator for an origin chain
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorStatus {
+    /// The [`crate::config::config_hash`] of the applied configs
+    pub config_hash: String,
+    /// The Docker state of the container (`running`, `exited`, ...), or `stopped`, `paused` or
+    /// `missing` if there is none
+    pub container_state: String,
+    /// How long the container has been running, in seconds
+    pub uptime_secs: Option<u64>,
+    /// The agent image the container runs
+    pub image: String,
+    /// The address the validator signs checkpoints with, or [`ADDRESS_UNAVAILABLE`] if it couldn't
+    /// be looked up
+    pub validator_address: String,
+    /// The index of the latest signed checkpoint, if the blueprint can read its checkpoints
+    pub latest_checkpoint_index: Option<u32>,
+}
+
+/// The Docker state of `container_id`
+pub struct ContainerState {
+    pub state: String,
+    pub uptime_secs: Option<u64>,
+    pub image: Option<String>,
+}
+
+/// Inspect `container_id`, or return `None` if it no longer exists
+pub async fn container_state(docker: &Docker, container_id: &str) -> Option<ContainerState> {
+    let inspect = docker.inspect_container(container_id, None).await.ok()?;
+    let state = inspect.state.unwrap_or_default();
+
+    let running = state.running.unwrap_or_default();
+    let uptime_secs = state
+        .started_at
+        .filter(|_| running)
+        .and_then(|started_at| DateTime::parse_from_rfc3339(&started_at).ok())
+        .and_then(|started_at| {
+            let uptime = Utc::now().signed_duration_since(started_at);
+            u64::try_from(uptime.num_seconds()).ok()
+        });
+
+    Some(ContainerState {
+        state: state
+            .status
+            .map_or_else(|| String::from("unknown"), |status| status.to_string()),
+        uptime_secs,
+        image: inspect.config.and_then(|config| config.image),
+    })
+}
+
+/// Whether `container_id` has `source` on the host mounted at `destination`
+pub async fn has_mount(
+    docker: &Docker,
+    container_id: &str,
+    source: &Path,
+    destination: &str,
+) -> bool {
+    let Ok(inspect) = docker.inspect_container(container_id, None).await else {
+        return false;
+    };
+
+    inspect.mounts.iter().flatten().any(|mount| {
+        mount.source.as_deref().map(Path::new) == Some(source)
+            && mount.destination.as_deref() == Some(destination)
+    })
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use blueprint_sdk::alloy::primitives::address;
+use common::template_config;
+use hyperlane_validator_blueprint_lib::announcement;
+use hyperlane_validator_blueprint_lib::config::CheckpointSyncerConfig;
+
+#[test]
+fn storage_locations() {
+    let cases = [
+        (
+            CheckpointSyncerConfig::LocalStorage {
+                path: String::from("/checkpoints"),
+            },
+            "file:///checkpoints",
+        ),
+        (
+            CheckpointSyncerConfig::S3 {
+                bucket: String::from("bucket"),
+                region: String::from("us-east-1"),
+                folder: None,
+            },
+            "s3://bucket/us-east-1",
+        ),
+        (
+            CheckpointSyncerConfig::S3 {
+                bucket: String::from("bucket"),
+                region: String::from("us-east-1"),
+                folder: Some(String::from("validator")),
+            },
+            "s3://bucket/us-east-1/validator",
+        ),
+        (
+            CheckpointSyncerConfig::Gcs {
+                bucket: String::from("bucket"),
+                folder: Some(String::from("validator")),
+                service_account_key: None,
+                user_secrets: None,
+            },
+            "gs://bucket/validator",
+        ),
+    ];
+
+    for (checkpoint_syncer, expected) in cases {
+        assert_eq!(checkpoint_syncer.storage_location(), expected);
+    }
+}
+
+#[test]
+fn announced() {
+    let announced = [
+        String::from("file:///old"),
+        String::from("s3://bucket/us-east-1/"),
+    ];
+    assert!(announcement::check_announced("s3://bucket/us-east-1", &announced).is_ok());
+    assert!(announcement::check_announced("file:///old", &announced).is_ok());
+}
+
+#[test]
+fn not_announced() {
+    let reason = announcement::check_announced("file:///checkpoints", &[]).unwrap_err();
+    assert!(reason.contains("hasn't announced"), "{reason}");
+
+    let announced = [String::from("s3://bucket/us-east-1")];
+    let reason = announcement::check_announced("file:///checkpoints", &announced).unwrap_err();
+    assert!(reason.contains("s3://bucket/us-east-1"), "{reason}");
+}
+
+#[test]
+fn validator_announce_from_config() {
+    let config = template_config();
+
+    let (rpc_url, validator_announce) =
+        announcement::validator_announce(&config, "testnet2").unwrap();
+    assert_eq!(rpc_url, "{TESTNET_2_RPC}");
+    assert_eq!(
+        validator_announce,
+        address!("0xc6e7DF5E7b4f2A278906862b61205850344D4e7d")
+    );
+}
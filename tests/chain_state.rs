@@ -0,0 +1,268 @@
+use blueprint_sdk::testing::tempfile;
+use hyperlane_validator_blueprint_lib::chain_state::{
+    self, AGENT_CONFIGS_DIR, AGENT_IMAGE_FILE, DbAction, HYPERLANE_DB_DIR,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lay out a chain as `set_config` and a running validator leave it
+fn configured_chain(chains_dir: &Path, origin_chain_name: &str) -> PathBuf {
+    let chain_dir = chains_dir.join(origin_chain_name);
+    fs::create_dir_all(chain_dir.join(AGENT_CONFIGS_DIR)).unwrap();
+    fs::write(chain_dir.join(AGENT_CONFIGS_DIR).join("0.json"), "{}").unwrap();
+    fs::write(chain_dir.join(AGENT_IMAGE_FILE), "agent@sha256:pinned").unwrap();
+    fs::create_dir_all(chain_dir.join(HYPERLANE_DB_DIR)).unwrap();
+    fs::write(chain_dir.join(HYPERLANE_DB_DIR).join("CURRENT"), "db").unwrap();
+    chain_dir
+}
+
+fn chains(names: &[&str]) -> HashSet<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn upgrade_rollback_restores_previous_image() {
+    let dir = tempfile::tempdir().unwrap();
+    let agent_image_path = dir.path().join("agent_image.txt");
+    fs::write(&agent_image_path, "agent@sha256:old").unwrap();
+
+    // As done by `upgrade_agent`
+    chain_state::backup(&agent_image_path).unwrap();
+    fs::write(&agent_image_path, "agent@sha256:new").unwrap();
+
+    chain_state::revert(&agent_image_path).unwrap();
+    assert_eq!(
+        fs::read_to_string(&agent_image_path).unwrap(),
+        "agent@sha256:old"
+    );
+    assert!(!chain_state::backup_path(&agent_image_path).exists());
+}
+
+#[test]
+fn rollback_without_backup_falls_back_to_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let agent_image_path = dir.path().join("agent_image.txt");
+
+    // The chain ran the operator's default image, so there was nothing to back up
+    chain_state::backup(&agent_image_path).unwrap();
+    fs::write(&agent_image_path, "agent@sha256:new").unwrap();
+
+    chain_state::revert(&agent_image_path).unwrap();
+    assert!(!agent_image_path.exists());
+}
+
+#[test]
+fn backup_replaces_old_backup() {
+    let dir = tempfile::tempdir().unwrap();
+    let configs_path = dir.path().join("agent_configs");
+
+    fs::create_dir(&configs_path).unwrap();
+    fs::write(configs_path.join("0.json"), "first").unwrap();
+    chain_state::backup(&configs_path).unwrap();
+
+    fs::create_dir(&configs_path).unwrap();
+    fs::write(configs_path.join("0.json"), "second").unwrap();
+    chain_state::backup(&configs_path).unwrap();
+    assert!(!configs_path.exists());
+
+    assert!(chain_state::restore_backup(&configs_path).unwrap());
+    assert_eq!(
+        fs::read_to_string(configs_path.join("0.json")).unwrap(),
+        "second"
+    );
+
+    // The backup was used up
+    assert!(!chain_state::restore_backup(&configs_path).unwrap());
+}
+
+#[test]
+fn db_actions() {
+    assert_eq!("keep".parse::<DbAction>().unwrap(), DbAction::Keep);
+    assert_eq!("archive".parse::<DbAction>().unwrap(), DbAction::Archive);
+    assert_eq!("delete".parse::<DbAction>().unwrap(), DbAction::Delete);
+    assert!("wipe".parse::<DbAction>().is_err());
+}
+
+#[test]
+fn stop_archives_db() {
+    let dir = tempfile::tempdir().unwrap();
+    let chain_dir = configured_chain(dir.path(), "testnet1");
+
+    // As done by `stop_validator`
+    chain_state::mark_stopped(&chain_dir).unwrap();
+    let archive_path = chain_state::apply_db_action(&chain_dir, DbAction::Archive)
+        .unwrap()
+        .unwrap();
+
+    assert!(!chain_dir.join(HYPERLANE_DB_DIR).exists());
+    assert_eq!(archive_path.parent().unwrap(), chain_dir);
+    assert!(
+        archive_path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("hyperlane_db.")
+    );
+    assert_eq!(
+        fs::read_to_string(archive_path.join("CURRENT")).unwrap(),
+        "db"
+    );
+
+    // The configs are kept for `set_config` to start it again
+    assert!(chain_state::is_configured(&chain_dir));
+    assert_eq!(
+        chain_state::configured_chains(dir.path()).unwrap(),
+        chains(&["testnet1"])
+    );
+    assert!(chain_state::active_chains(dir.path()).unwrap().is_empty());
+}
+
+#[test]
+fn stop_deletes_db() {
+    let dir = tempfile::tempdir().unwrap();
+    let chain_dir = configured_chain(dir.path(), "testnet1");
+
+    chain_state::mark_stopped(&chain_dir).unwrap();
+    assert!(
+        chain_state::apply_db_action(&chain_dir, DbAction::Delete)
+            .unwrap()
+            .is_none()
+    );
+
+    assert!(!chain_dir.join(HYPERLANE_DB_DIR).exists());
+    assert!(chain_state::is_configured(&chain_dir));
+}
+
+#[test]
+fn stop_keeps_db() {
+    let dir = tempfile::tempdir().unwrap();
+    let chain_dir = configured_chain(dir.path(), "testnet1");
+
+    chain_state::mark_stopped(&chain_dir).unwrap();
+    assert!(
+        chain_state::apply_db_action(&chain_dir, DbAction::Keep)
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(
+        fs::read_to_string(chain_dir.join(HYPERLANE_DB_DIR).join("CURRENT")).unwrap(),
+        "db"
+    );
+
+    // Nothing to archive is fine too
+    chain_state::apply_db_action(&chain_dir, DbAction::Delete).unwrap();
+    assert!(
+        chain_state::apply_db_action(&chain_dir, DbAction::Archive)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn stopped_marker_survives_reconcile() {
+    let dir = tempfile::tempdir().unwrap();
+    let stopped = configured_chain(dir.path(), "testnet1");
+    configured_chain(dir.path(), "testnet2");
+
+    chain_state::mark_stopped(&stopped).unwrap();
+
+    // Reconciling on startup only restores active chains, and the marker is only read from disk
+    for _ in 0..2 {
+        assert_eq!(
+            chain_state::active_chains(dir.path()).unwrap(),
+            chains(&["testnet2"])
+        );
+        assert!(chain_state::is_stopped(&stopped));
+    }
+
+    // New configs start it again
+    chain_state::clear_markers(&stopped).unwrap();
+    assert_eq!(
+        chain_state::active_chains(dir.path()).unwrap(),
+        chains(&["testnet1", "testnet2"])
+    );
+}
+
+#[test]
+fn pause_and_resume_keep_image_and_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let chain_dir = configured_chain(dir.path(), "testnet1");
+    let agent_image_path = chain_dir.join("agent_image.txt");
+    let config_path = chain_dir.join(AGENT_CONFIGS_DIR).join("0.json");
+
+    chain_state::mark_paused(&chain_dir, "agent@sha256:pinned").unwrap();
+    assert!(chain_state::is_paused(&chain_dir));
+    assert!(chain_state::active_chains(dir.path()).unwrap().is_empty());
+
+    // As done by `resume_validator` once the validator started
+    chain_state::clear_paused(&chain_dir).unwrap();
+    assert!(!chain_state::is_paused(&chain_dir));
+    assert_eq!(
+        chain_state::active_chains(dir.path()).unwrap(),
+        chains(&["testnet1"])
+    );
+
+    assert_eq!(
+        fs::read_to_string(agent_image_path).unwrap(),
+        "agent@sha256:pinned"
+    );
+    assert_eq!(fs::read_to_string(config_path).unwrap(), "{}");
+    assert_eq!(
+        fs::read_to_string(chain_dir.join(HYPERLANE_DB_DIR).join("CURRENT")).unwrap(),
+        "db"
+    );
+}
+
+#[test]
+fn resume_uses_image_running_when_paused() {
+    let dir = tempfile::tempdir().unwrap();
+    let chain_dir = configured_chain(dir.path(), "testnet1");
+
+    // The validator ran the operator's default image, so none was set for the chain
+    fs::remove_file(chain_dir.join(AGENT_IMAGE_FILE)).unwrap();
+    assert!(chain_state::agent_image(&chain_dir).unwrap().is_none());
+
+    chain_state::mark_paused(&chain_dir, "agent@sha256:running").unwrap();
+
+    // `resume_validator` starts the recorded image rather than whatever the default is by then
+    assert_eq!(
+        chain_state::agent_image(&chain_dir).unwrap().as_deref(),
+        Some("agent@sha256:running")
+    );
+
+    // Nothing is recorded if the pause is rejected
+    let stopped = configured_chain(dir.path(), "testnet2");
+    chain_state::mark_stopped(&stopped).unwrap();
+    assert!(chain_state::mark_paused(&stopped, "agent@sha256:other").is_err());
+    assert_eq!(
+        chain_state::agent_image(&stopped).unwrap().as_deref(),
+        Some("agent@sha256:pinned")
+    );
+}
+
+#[test]
+fn pause_requires_running_validator() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let unconfigured = dir.path().join("testnet1");
+    fs::create_dir_all(&unconfigured).unwrap();
+    assert!(chain_state::mark_paused(&unconfigured, "agent@sha256:pinned").is_err());
+
+    let chain_dir = configured_chain(dir.path(), "testnet2");
+    chain_state::mark_stopped(&chain_dir).unwrap();
+    assert!(chain_state::mark_paused(&chain_dir, "agent@sha256:pinned").is_err());
+    assert!(!chain_state::is_paused(&chain_dir));
+}
+
+#[test]
+fn stop_replaces_pause() {
+    let dir = tempfile::tempdir().unwrap();
+    let chain_dir = configured_chain(dir.path(), "testnet1");
+
+    chain_state::mark_paused(&chain_dir, "agent@sha256:pinned").unwrap();
+    chain_state::mark_stopped(&chain_dir).unwrap();
+
+    assert!(chain_state::is_stopped(&chain_dir));
+    assert!(!chain_state::is_paused(&chain_dir));
+}
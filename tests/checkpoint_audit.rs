@@ -0,0 +1,136 @@
+mod common;
+
+use blueprint_sdk::alloy::signers::local::PrivateKeySigner;
+use blueprint_sdk::testing::tempfile;
+use color_eyre::Result;
+use common::{sign_checkpoint, sign_up_to};
+use hyperlane_validator_blueprint_lib::checkpoint::LATEST_INDEX_FILE;
+use hyperlane_validator_blueprint_lib::checkpoint_audit::{self, AUDIT_WINDOW, AuditStatus};
+use hyperlane_validator_blueprint_lib::checkpoint_mirror::Store;
+use std::fs;
+
+#[test]
+fn recovers_signer() -> Result<()> {
+    let signer = PrivateKeySigner::random();
+    let signed = sign_checkpoint(&signer, 3)?;
+    assert_eq!(signed.recover_signer()?, signer.address());
+
+    // Any change to the checkpoint changes the signer
+    let mut tampered = signed.clone();
+    tampered.value.checkpoint.index = 4;
+    assert_ne!(tampered.recover_signer()?, signer.address());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn healthy_checkpoints() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let signer = PrivateKeySigner::random();
+    sign_up_to(&signer, dir.path(), 2)?;
+
+    let source = Store::Local(dir.path().to_path_buf());
+    let mut status = AuditStatus::default();
+    let problems = checkpoint_audit::audit(&source, signer.address(), &mut status).await?;
+
+    assert!(problems.is_empty(), "{problems:?}");
+    assert!(status.healthy);
+    assert_eq!(status.latest_index, Some(2));
+    assert_eq!(status.checked, 3);
+    assert_eq!(status.failures, 0);
+
+    // Only new checkpoints are audited on later passes
+    sign_up_to(&signer, dir.path(), 4)?;
+    checkpoint_audit::audit(&source, signer.address(), &mut status).await?;
+    assert_eq!(status.latest_index, Some(4));
+    assert_eq!(status.checked, 5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn only_newest_checkpoints_are_audited() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let signer = PrivateKeySigner::random();
+    sign_up_to(&signer, dir.path(), AUDIT_WINDOW * 3)?;
+
+    let source = Store::Local(dir.path().to_path_buf());
+    let mut status = AuditStatus::default();
+    checkpoint_audit::audit(&source, signer.address(), &mut status).await?;
+
+    assert!(status.healthy);
+    assert_eq!(status.checked, u64::from(AUDIT_WINDOW));
+    assert_eq!(status.latest_index, Some(AUDIT_WINDOW * 3));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn wrong_signer() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let signer = PrivateKeySigner::random();
+    sign_up_to(&signer, dir.path(), 1)?;
+
+    let expected = PrivateKeySigner::random().address();
+    let source = Store::Local(dir.path().to_path_buf());
+    let mut status = AuditStatus::default();
+    let problems = checkpoint_audit::audit(&source, expected, &mut status).await?;
+
+    assert_eq!(problems.len(), 2);
+    assert!(!status.healthy);
+    assert_eq!(status.failures, 2);
+    let last_failure = status.last_failure.unwrap();
+    assert!(
+        last_failure.contains(&signer.address().to_string()),
+        "{last_failure}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn index_going_backwards() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let signer = PrivateKeySigner::random();
+    sign_up_to(&signer, dir.path(), 5)?;
+
+    let source = Store::Local(dir.path().to_path_buf());
+    let mut status = AuditStatus::default();
+    checkpoint_audit::audit(&source, signer.address(), &mut status).await?;
+    assert!(status.healthy);
+
+    fs::write(dir.path().join(LATEST_INDEX_FILE), "3")?;
+    let problems = checkpoint_audit::audit(&source, signer.address(), &mut status).await?;
+
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("backwards"), "{}", problems[0]);
+    assert!(!status.healthy);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn nothing_published() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let source = Store::Local(dir.path().to_path_buf());
+    let mut status = AuditStatus::default();
+    let problems =
+        checkpoint_audit::audit(&source, PrivateKeySigner::random().address(), &mut status).await?;
+
+    // Nothing is published until a message has been dispatched
+    assert!(problems.is_empty(), "{problems:?}");
+    assert!(status.healthy);
+    assert!(status.latest_index.is_none());
+    assert_eq!(status.checked, 0);
+
+    // Once it is, the first checkpoints are audited
+    let signer = PrivateKeySigner::random();
+    sign_up_to(&signer, dir.path(), 0)?;
+    checkpoint_audit::audit(&source, signer.address(), &mut status).await?;
+    assert!(status.healthy);
+    assert_eq!(status.latest_index, Some(0));
+    assert_eq!(status.checked, 1);
+
+    Ok(())
+}
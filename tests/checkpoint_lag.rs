@@ -0,0 +1,37 @@
+mod common;
+
+use blueprint_sdk::alloy::primitives::address;
+use common::template_config;
+use hyperlane_validator_blueprint_lib::checkpoint_lag::{self, LagStatus};
+
+#[test]
+fn lag() {
+    // Nothing dispatched, nothing to sign
+    assert_eq!(checkpoint_lag::lag(0, None), 0);
+    assert_eq!(checkpoint_lag::lag(3, None), 3);
+    assert_eq!(checkpoint_lag::lag(3, Some(0)), 2);
+    assert_eq!(checkpoint_lag::lag(3, Some(2)), 0);
+
+    // The tree count may be read before the checkpoint is
+    assert_eq!(checkpoint_lag::lag(3, Some(5)), 0);
+}
+
+#[test]
+fn behind_threshold() {
+    assert!(!LagStatus::new(10, Some(4), 5).behind);
+    assert!(LagStatus::new(10, Some(3), 5).behind);
+}
+
+#[test]
+fn merkle_tree_hook_from_config() {
+    let (rpc_url, merkle_tree_hook) =
+        checkpoint_lag::merkle_tree_hook(&template_config(), "testnet1").unwrap();
+    assert_eq!(rpc_url, "{TESTNET_1_RPC}");
+    assert_eq!(
+        merkle_tree_hook,
+        address!("0x0DCd1Bf9A1b36cE34237eEaFef220932846BCD82")
+    );
+
+    let err = checkpoint_lag::merkle_tree_hook(&template_config(), "testnet3").unwrap_err();
+    assert!(err.to_string().contains("testnet3"));
+}
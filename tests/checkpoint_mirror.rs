@@ -0,0 +1,179 @@
+mod common;
+
+use blueprint_sdk::alloy::signers::local::PrivateKeySigner;
+use blueprint_sdk::testing::tempfile;
+use color_eyre::Result;
+use common::{sign_checkpoint, sign_up_to};
+use hyperlane_validator_blueprint_lib::checkpoint::{self, ANNOUNCEMENT_FILE, LATEST_INDEX_FILE};
+use hyperlane_validator_blueprint_lib::checkpoint_mirror::{
+    self, CHECKPOINTS_PER_BATCH, MirrorState, Store,
+};
+use std::fs;
+
+#[tokio::test]
+async fn mirrors_checkpoints() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let source_path = dir.path().join("source");
+    let mirror_path = dir.path().join("mirror");
+    let signer = PrivateKeySigner::random();
+
+    sign_up_to(&signer, &source_path, 2)?;
+    fs::write(source_path.join(ANNOUNCEMENT_FILE), r#"{"announcement":1}"#)?;
+
+    let source = Store::Local(source_path.clone());
+    let mirror = Store::Local(mirror_path.clone());
+    let mut state = MirrorState::default();
+    checkpoint_mirror::sync(&source, &mirror, &mut state, CHECKPOINTS_PER_BATCH).await?;
+
+    assert_eq!(state.latest_index, Some(2));
+    for file_name in [
+        checkpoint::checkpoint_file(0),
+        checkpoint::checkpoint_file(1),
+        checkpoint::checkpoint_file(2),
+        String::from(LATEST_INDEX_FILE),
+        String::from(ANNOUNCEMENT_FILE),
+    ] {
+        assert_eq!(
+            fs::read(source_path.join(&file_name))?,
+            fs::read(mirror_path.join(&file_name))?,
+            "{file_name}"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn catches_up() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let source_path = dir.path().join("source");
+    let mirror_path = dir.path().join("mirror");
+    let signer = PrivateKeySigner::random();
+
+    sign_up_to(&signer, &source_path, 1)?;
+
+    let source = Store::Local(source_path.clone());
+    let mirror = Store::Local(mirror_path.clone());
+    let mut state = MirrorState::default();
+    checkpoint_mirror::sync(&source, &mirror, &mut state, CHECKPOINTS_PER_BATCH).await?;
+    assert_eq!(state.latest_index, Some(1));
+
+    // Already mirrored checkpoints aren't copied again
+    fs::remove_file(mirror_path.join(checkpoint::checkpoint_file(0)))?;
+
+    sign_up_to(&signer, &source_path, 4)?;
+    checkpoint_mirror::sync(&source, &mirror, &mut state, CHECKPOINTS_PER_BATCH).await?;
+
+    assert_eq!(state.latest_index, Some(4));
+    assert!(!mirror_path.join(checkpoint::checkpoint_file(0)).exists());
+    for index in 2..=4 {
+        assert!(
+            mirror_path
+                .join(checkpoint::checkpoint_file(index))
+                .exists()
+        );
+    }
+    assert_eq!(
+        fs::read_to_string(mirror_path.join(LATEST_INDEX_FILE))?,
+        "4"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rejects_bad_checkpoints() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let source_path = dir.path().join("source");
+    let mirror_path = dir.path().join("mirror");
+    let signer = PrivateKeySigner::random();
+
+    sign_up_to(&signer, &source_path, 2)?;
+
+    // Checkpoint 1 holds the wrong checkpoint
+    fs::write(
+        source_path.join(checkpoint::checkpoint_file(1)),
+        serde_json::to_vec(&sign_checkpoint(&signer, 2)?)?,
+    )?;
+
+    let source = Store::Local(source_path);
+    let mirror = Store::Local(mirror_path.clone());
+    let mut state = MirrorState::default();
+    let err = checkpoint_mirror::sync(&source, &mirror, &mut state, CHECKPOINTS_PER_BATCH)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("index 1"), "{err}");
+
+    // Progress stops before the bad checkpoint, and the latest index isn't advertised
+    assert_eq!(state.latest_index, Some(0));
+    assert!(!mirror_path.join(checkpoint::checkpoint_file(1)).exists());
+    assert!(!mirror_path.join(LATEST_INDEX_FILE).exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn copies_in_batches() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let source_path = dir.path().join("source");
+    let mirror_path = dir.path().join("mirror");
+    let signer = PrivateKeySigner::random();
+
+    sign_up_to(&signer, &source_path, 4)?;
+
+    let source = Store::Local(source_path);
+    let mirror = Store::Local(mirror_path.clone());
+    let mut state = MirrorState::default();
+
+    // Not caught up yet, so the latest index isn't advertised
+    assert!(!checkpoint_mirror::sync(&source, &mirror, &mut state, 2).await?);
+    assert_eq!(state.latest_index, Some(1));
+    assert!(!mirror_path.join(checkpoint::checkpoint_file(2)).exists());
+    assert!(!mirror_path.join(LATEST_INDEX_FILE).exists());
+
+    // The next batch picks up where the last one stopped
+    assert!(!checkpoint_mirror::sync(&source, &mirror, &mut state, 2).await?);
+    assert_eq!(state.latest_index, Some(3));
+
+    assert!(checkpoint_mirror::sync(&source, &mirror, &mut state, 2).await?);
+    assert_eq!(state.latest_index, Some(4));
+    assert_eq!(
+        fs::read_to_string(mirror_path.join(LATEST_INDEX_FILE))?,
+        "4"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn stops_at_missing_checkpoint() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let source_path = dir.path().join("source");
+    let mirror_path = dir.path().join("mirror");
+    let signer = PrivateKeySigner::random();
+
+    sign_up_to(&signer, &source_path, 3)?;
+    fs::remove_file(source_path.join(checkpoint::checkpoint_file(2)))?;
+
+    let source = Store::Local(source_path.clone());
+    let mirror = Store::Local(mirror_path.clone());
+    let mut state = MirrorState::default();
+    let err = checkpoint_mirror::sync(&source, &mirror, &mut state, CHECKPOINTS_PER_BATCH)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Checkpoint 2"), "{err}");
+
+    assert_eq!(state.latest_index, Some(1));
+    assert!(!mirror_path.join(checkpoint::checkpoint_file(3)).exists());
+    assert!(!mirror_path.join(LATEST_INDEX_FILE).exists());
+
+    // Once it shows up, the mirror carries on from it
+    fs::write(
+        source_path.join(checkpoint::checkpoint_file(2)),
+        serde_json::to_vec(&sign_checkpoint(&signer, 2)?)?,
+    )?;
+    assert!(checkpoint_mirror::sync(&source, &mirror, &mut state, CHECKPOINTS_PER_BATCH).await?);
+    assert_eq!(state.latest_index, Some(3));
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use blueprint_sdk::testing::tempfile;
+use color_eyre::Result;
+use hyperlane_validator_blueprint_lib::checkpoint_server;
+use reqwest::StatusCode;
+use std::fs;
+use tokio::net::TcpListener;
+
+const LATEST_INDEX: &str = "5";
+
+async fn start_server() -> Result<(tempfile::TempDir, String)> {
+    let chains_dir = tempfile::tempdir()?;
+
+    let checkpoints_path = chains_dir.path().join("testnet1").join("checkpoints");
+    fs::create_dir_all(&checkpoints_path)?;
+    fs::write(
+        checkpoints_path.join("checkpoint_latest_index.json"),
+        LATEST_INDEX,
+    )?;
+
+    // Outside of any checkpoints directory
+    fs::write(chains_dir.path().join("secret.json"), "secret")?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(checkpoint_server::serve(
+        listener,
+        chains_dir.path().to_path_buf(),
+    ));
+
+    Ok((chains_dir, url))
+}
+
+#[tokio::test]
+async fn serves_checkpoints() -> Result<()> {
+    let (_chains_dir, url) = start_server().await?;
+
+    let response = reqwest::get(format!("{url}/testnet1/checkpoint_latest_index.json")).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().await?, LATEST_INDEX);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn missing_checkpoint() -> Result<()> {
+    let (_chains_dir, url) = start_server().await?;
+
+    for path in [
+        "testnet1/checkpoint_6_with_id.json",
+        "testnet2/checkpoint_latest_index.json",
+    ] {
+        let response = reqwest::get(format!("{url}/{path}")).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{path}");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn only_checkpoint_files_are_served() -> Result<()> {
+    let (_chains_dir, url) = start_server().await?;
+
+    for path in [
+        "testnet1/..%2F..%2Fsecret.json",
+        "..%2Fsecret.json/checkpoint_latest_index.json",
+        "testnet1/checkpoint_latest_index",
+    ] {
+        let response = reqwest::get(format!("{url}/{path}")).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{path}");
+    }
+
+    // Writes aren't allowed
+    let response = reqwest::Client::new()
+        .put(format!("{url}/testnet1/checkpoint_latest_index.json"))
+        .body("6")
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use hyperlane_validator_blueprint_lib::checkpoint_syncer;
+use hyperlane_validator_blueprint_lib::config::CheckpointSyncerConfig;
+
+#[test]
+fn s3_settings() {
+    let syncer = checkpoint_syncer::parse(
+        r#"{ "type": "s3", "bucket": "signatures", "region": "us-east-1", "folder": "ethereum" }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        syncer,
+        CheckpointSyncerConfig::S3 {
+            bucket: String::from("signatures"),
+            region: String::from("us-east-1"),
+            folder: Some(String::from("ethereum")),
+        }
+    );
+}
+
+#[test]
+fn s3_needs_bucket_and_region() {
+    let err = checkpoint_syncer::parse(r#"{ "type": "s3", "bucket": "signatures" }"#).unwrap_err();
+    assert!(err.to_string().contains("region"), "{err}");
+
+    let err = checkpoint_syncer::parse(r#"{ "type": "s3", "bucket": "", "region": "us-east-1" }"#)
+        .unwrap_err();
+    assert!(err.to_string().contains("bucket"), "{err}");
+}
+
+#[test]
+fn gcs_credentials_are_rejected() {
+    let err = checkpoint_syncer::parse(
+        r#"{ "type": "gcs", "bucket": "signatures", "serviceAccountKey": "/etc/key.json" }"#,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("serviceAccountKey"), "{err}");
+}
+
+#[test]
+fn unknown_type() {
+    let err = checkpoint_syncer::parse(r#"{ "type": "ipfs" }"#).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Invalid checkpoint syncer settings"),
+        "{err}"
+    );
+}
@@ -0,0 +1,66 @@
+//! Fixtures shared by the integration tests
+
+#![allow(dead_code)]
+
+use blueprint_sdk::alloy::primitives::{B256, U256};
+use blueprint_sdk::alloy::signers::SignerSync;
+use blueprint_sdk::alloy::signers::local::PrivateKeySigner;
+use color_eyre::Result;
+use hyperlane_validator_blueprint_lib::checkpoint::{
+    self, Checkpoint, CheckpointSignature, CheckpointWithMessageId, LATEST_INDEX_FILE,
+    SignedCheckpointWithMessageId,
+};
+use hyperlane_validator_blueprint_lib::config::AgentConfig;
+use std::fs;
+use std::path::Path;
+
+pub const AGENT_CONFIG_TEMPLATE_PATH: &str = "./test_assets/agent-config.json.template";
+
+/// The raw agent config template
+pub fn template() -> String {
+    fs::read_to_string(AGENT_CONFIG_TEMPLATE_PATH).unwrap()
+}
+
+/// The agent config template, parsed
+pub fn template_config() -> AgentConfig {
+    AgentConfig::merged(&[template()]).unwrap()
+}
+
+pub fn sign_checkpoint(
+    signer: &PrivateKeySigner,
+    index: u32,
+) -> Result<SignedCheckpointWithMessageId> {
+    let value = CheckpointWithMessageId {
+        checkpoint: Checkpoint {
+            merkle_tree_hook_address: B256::with_last_byte(1),
+            mailbox_domain: 31337,
+            root: B256::from(U256::from(index + 100)),
+            index,
+        },
+        message_id: B256::from(U256::from(index + 200)),
+    };
+
+    let signature = signer.sign_message_sync(value.signing_hash().as_slice())?;
+    Ok(SignedCheckpointWithMessageId {
+        value,
+        signature: CheckpointSignature {
+            r: signature.r(),
+            s: signature.s(),
+            v: 27 + u64::from(signature.v()),
+        },
+    })
+}
+
+/// Write checkpoints up to `latest` as the validator would
+pub fn sign_up_to(signer: &PrivateKeySigner, path: &Path, latest: u32) -> Result<()> {
+    fs::create_dir_all(path)?;
+    for index in 0..=latest {
+        fs::write(
+            path.join(checkpoint::checkpoint_file(index)),
+            serde_json::to_vec(&sign_checkpoint(signer, index)?)?,
+        )?;
+    }
+
+    fs::write(path.join(LATEST_INDEX_FILE), latest.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,170 @@
+mod common;
+
+use blueprint_sdk::testing::tempfile;
+use common::template;
+use hyperlane_validator_blueprint_lib::config::{
+    AgentConfig, CheckpointSyncerConfig, RpcConsensusType, config_files, config_hash, load_merged,
+    validate_configs,
+};
+use std::fs;
+
+#[test]
+fn template_is_valid() {
+    validate_configs(&[template()], "testnet1").unwrap();
+    validate_configs(&[template()], "testnet2").unwrap();
+}
+
+#[test]
+fn no_configs_is_valid() {
+    validate_configs(&[], "testnet1").unwrap();
+}
+
+#[test]
+fn unknown_origin_chain() {
+    let err = validate_configs(&[template()], "testnet3").unwrap_err();
+    assert!(err.to_string().contains("testnet3"));
+}
+
+#[test]
+fn invalid_json() {
+    let err = validate_configs(&[String::from("{ \"chains\": ")], "testnet1").unwrap_err();
+    assert!(err.to_string().contains("not valid JSON"));
+}
+
+#[test]
+fn missing_required_fields() {
+    for field in ["mailbox", "merkleTreeHook", "validatorAnnounce"] {
+        let mut config: serde_json::Value = serde_json::from_str(&template()).unwrap();
+        config["chains"]["testnet2"]
+            .as_object_mut()
+            .unwrap()
+            .remove(field);
+
+        let err = validate_configs(&[config.to_string()], "testnet1").unwrap_err();
+        assert!(err.to_string().contains(field), "{err}");
+    }
+}
+
+#[test]
+fn empty_rpc_urls() {
+    let mut config: serde_json::Value = serde_json::from_str(&template()).unwrap();
+    config["chains"]["testnet1"]["rpcUrls"] = serde_json::json!([]);
+
+    let err = validate_configs(&[config.to_string()], "testnet1").unwrap_err();
+    assert!(err.to_string().contains("rpcUrls"));
+}
+
+#[test]
+fn configs_are_merged() {
+    let mut base: serde_json::Value = serde_json::from_str(&template()).unwrap();
+    base["chains"]["testnet1"]["rpcUrls"] = serde_json::json!([]);
+
+    // A later config can fill in what an earlier one is missing
+    let overlay = serde_json::json!({
+        "chains": {
+            "testnet1": {
+                "rpcUrls": [{ "http": "http://127.0.0.1:8545" }]
+            }
+        }
+    });
+
+    validate_configs(&[base.to_string(), overlay.to_string()], "testnet1").unwrap();
+}
+
+#[test]
+fn template_round_trips() {
+    let original: serde_json::Value = serde_json::from_str(&template()).unwrap();
+
+    let config: AgentConfig = serde_json::from_value(original.clone()).unwrap();
+    assert_eq!(config.chains.len(), 2);
+    assert_eq!(
+        config.default_rpc_consensus_type,
+        Some(RpcConsensusType::Fallback)
+    );
+    assert_eq!(
+        config.checkpoint_syncer,
+        Some(CheckpointSyncerConfig::LocalStorage {
+            path: String::from("{TMP_SYNCER_DIR}")
+        })
+    );
+
+    let testnet1 = &config.chains["testnet1"];
+    assert_eq!(testnet1.domain_id, Some(31337));
+    assert_eq!(testnet1.rpc_urls[0].http, "{TESTNET_1_RPC}");
+    assert_eq!(testnet1.index.as_ref().unwrap().from, Some(11));
+    assert_eq!(
+        testnet1.mailbox.as_deref(),
+        Some("0xB7f8BC63BbcaD18155201308C8f3540b07f84F5e")
+    );
+
+    assert_eq!(serde_json::to_value(&config).unwrap(), original);
+}
+
+#[test]
+fn config_files_sorted_by_index() {
+    let dir = tempfile::tempdir().unwrap();
+    for index in 0..=11 {
+        fs::write(dir.path().join(format!("{index}.json")), "{}").unwrap();
+    }
+
+    let names = config_files(dir.path())
+        .unwrap()
+        .into_iter()
+        .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    let expected = (0..=11)
+        .map(|index| format!("{index}.json"))
+        .collect::<Vec<_>>();
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn overlapping_configs_hash_in_index_order() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // Every config after the template moves the checkpoints, so only the last one should count
+    let mut configs = vec![template()];
+    for index in 1..=10 {
+        configs.push(
+            serde_json::json!({
+                "checkpointSyncer": {
+                    "type": "localStorage",
+                    "path": format!("/checkpoints/{index}"),
+                }
+            })
+            .to_string(),
+        );
+    }
+
+    // As written by `set_config`
+    for (index, config) in configs.iter().enumerate() {
+        fs::write(dir.path().join(format!("{index}.json")), config).unwrap();
+    }
+
+    let applied = load_merged(dir.path()).unwrap();
+    assert_eq!(
+        applied.checkpoint_syncer,
+        Some(CheckpointSyncerConfig::LocalStorage {
+            path: String::from("/checkpoints/10")
+        })
+    );
+
+    // The hash of the running validator matches the hash of the configs as submitted
+    let submitted = AgentConfig::merged(&configs).unwrap();
+    assert_eq!(
+        config_hash(&applied, "testnet1").unwrap(),
+        config_hash(&submitted, "testnet1").unwrap()
+    );
+
+    // Sorting by name would put `10.json` before `2.json`
+    let mut by_name = configs.iter().enumerate().collect::<Vec<_>>();
+    by_name.sort_by_key(|(index, _)| format!("{index}.json"));
+    let by_name = by_name
+        .into_iter()
+        .map(|(_, config)| config.clone())
+        .collect::<Vec<_>>();
+    assert_ne!(
+        config_hash(&AgentConfig::merged(&by_name).unwrap(), "testnet1").unwrap(),
+        config_hash(&submitted, "testnet1").unwrap()
+    );
+}
@@ -1,3 +1,5 @@
+use aws_sdk_kms::types::{KeySpec, KeyUsageType};
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
 use blueprint_sdk as sdk;
 use color_eyre::Report;
 use color_eyre::Result;
@@ -7,6 +9,7 @@ use docktopus::bollard::models::EndpointSettings;
 use docktopus::bollard::network::{
     ConnectNetworkOptions, CreateNetworkOptions, InspectNetworkOptions,
 };
+use docktopus::bollard::system::EventsOptions;
 use futures::StreamExt;
 use hyperlane_validator_blueprint_lib as blueprint;
 use sdk::Job;
@@ -24,12 +27,13 @@ use sdk::keystore::backends::Backend;
 use sdk::runner::config::BlueprintEnvironment;
 use sdk::tangle::extract::TangleArgs2;
 use sdk::tangle::layers::TangleLayer;
-use sdk::tangle::serde::to_field;
+use sdk::tangle::serde::{from_field, to_field};
 use sdk::testing::chain_setup::anvil::AnvilTestnet;
 use sdk::testing::chain_setup::anvil::start_anvil_container;
 use sdk::testing::tempfile::{self, TempDir};
 use sdk::testing::utils::setup_log;
 use sdk::testing::utils::tangle::{OutputValue, TangleTestHarness};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
@@ -38,6 +42,10 @@ use std::sync::Arc;
 use std::time::Duration;
 use testcontainers::ContainerAsync;
 use testcontainers::GenericImage;
+use testcontainers::ImageExt;
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use tokio::sync::Mutex;
 
 const AGENT_CONFIG_TEMPLATE_PATH: &str = "./test_assets/agent-config.json.template";
 const TEST_ASSETS_PATH: &str = "./test_assets";
@@ -267,15 +275,47 @@ async fn spinup_relayer(
     Ok(())
 }
 
+/// The tests share Docker networks, so they can't run at the same time
+static DOCKER_NETWORKS: Mutex<()> = Mutex::const_new(());
+
 #[tokio::test(flavor = "multi_thread")]
 async fn validator() -> Result<()> {
-    color_eyre::install()?;
+    let _ = color_eyre::install();
     setup_log();
+    let _networks = DOCKER_NETWORKS.lock().await;
 
     // Test logic is separated so that cleanup is performed regardless of failure
     let res = validator_test_inner().await;
 
-    // Cleanup networks
+    cleanup_networks().await?;
+    res
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validator_s3_checkpoint_syncer() -> Result<()> {
+    let _ = color_eyre::install();
+    setup_log();
+    let _networks = DOCKER_NETWORKS.lock().await;
+
+    let res = s3_checkpoint_syncer_test_inner().await;
+
+    cleanup_networks().await?;
+    res
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validator_kms_signer() -> Result<()> {
+    let _ = color_eyre::install();
+    setup_log();
+    let _networks = DOCKER_NETWORKS.lock().await;
+
+    let res = kms_signer_test_inner().await;
+
+    cleanup_networks().await?;
+    res
+}
+
+async fn cleanup_networks() -> Result<()> {
     let connection = DockerBuilder::new().await?;
     for network_name in [VALIDATOR_NETWORK_NAME, RELAYER_NETWORK_NAME] {
         let network = connection
@@ -298,7 +338,7 @@ async fn validator() -> Result<()> {
         connection.remove_network(network_name).await?;
     }
 
-    res
+    Ok(())
 }
 
 sol!(
@@ -339,29 +379,31 @@ fn wallet_for(key: &str, rpc: &str) -> (EthereumWallet, RootProvider) {
 const TESTNET1_MAILBOX: Address = address!("0xB7f8BC63BbcaD18155201308C8f3540b07f84F5e");
 const MESSAGE: &str = "Hello";
 
-async fn validator_test_inner() -> Result<()> {
-    let (origin_testnet, dest_testnet) = spinup_anvil_testnets().await?;
+/// The RPC URLs of `testnet`, from inside the validator's Docker network, and from the host
+///
+/// The validator itself uses the IPs internal to the Docker network.
+/// When it comes time to relay the message, the command is run outside the Docker network,
+/// so we need to get both addresses.
+///
+/// The internal address is written to `agent-config.json`.
+/// The host addresses are written to `testnet{1,2}-metadata.yaml`.
+async fn rpc_urls(testnet: &Testnet) -> Result<(String, String)> {
+    let docker_rpc_url = format!("http://{}:8545", testnet.validator_network_ip);
+
+    let ports = testnet.inner.container.ports().await?;
+    let host_rpc_url = format!(
+        "http://127.0.0.1:{}",
+        ports.map_to_host_port_ipv4(8545).unwrap()
+    );
 
-    // The validator itself uses the IPs internal to the Docker network.
-    // When it comes time to relay the message, the command is run outside the Docker network,
-    // so we need to get both addresses.
-    //
-    // The internal address is written to `agent-config.json`.
-    // The host addresses are written to `testnet{1,2}-metadata.yaml`.
-    let testnet1_docker_rpc_url = format!("http://{}:8545", origin_testnet.validator_network_ip);
-    let testnet2_docker_rpc_url = format!("http://{}:8545", dest_testnet.validator_network_ip);
+    Ok((docker_rpc_url, host_rpc_url))
+}
 
-    let origin_ports = origin_testnet.inner.container.ports().await?;
-    let dest_ports = dest_testnet.inner.container.ports().await?;
+async fn validator_test_inner() -> Result<()> {
+    let (origin_testnet, dest_testnet) = spinup_anvil_testnets().await?;
 
-    let testnet1_host_rpc_url = format!(
-        "http://127.0.0.1:{}",
-        origin_ports.map_to_host_port_ipv4(8545).unwrap()
-    );
-    let testnet2_host_rpc_url = format!(
-        "http://127.0.0.1:{}",
-        dest_ports.map_to_host_port_ipv4(8545).unwrap()
-    );
+    let (testnet1_docker_rpc_url, testnet1_host_rpc_url) = rpc_urls(&origin_testnet).await?;
+    let (testnet2_docker_rpc_url, testnet2_host_rpc_url) = rpc_urls(&dest_testnet).await?;
 
     let tempdir = setup_temp_dir(
         (testnet1_docker_rpc_url, testnet1_host_rpc_url.clone()),
@@ -378,6 +420,12 @@ async fn validator_test_inner() -> Result<()> {
         .add_job(blueprint::set_config.layer(TangleLayer))
         .await;
 
+    // The test chains' ISMs and funds are set up for the operator's own key
+    fs::write(
+        temp_dir_path.join(blueprint::operator::OperatorConfig::FILE_NAME),
+        r#"{ "signer": { "type": "keystore" } }"#,
+    )?;
+
     let ctx =
         blueprint::HyperlaneContext::new(harness.env().clone(), temp_dir_path.clone()).await?;
 
@@ -393,14 +441,34 @@ async fn validator_test_inner() -> Result<()> {
 
     // Execute job and verify result
     let call = harness
-        .submit_job(service_id, 0, vec![config_urls, origin_chain_name])
+        .submit_job(
+            service_id,
+            0,
+            vec![config_urls.clone(), origin_chain_name.clone()],
+        )
         .await?;
 
     let results = harness.wait_for_job_execution(0, call).await?;
 
-    harness.verify_job(&results, vec![OutputValue::Uint64(0)]);
+    let result: blueprint::SetConfigResult = from_field(results.result[0].clone())?;
+    assert_eq!(result.status, blueprint::ConfigStatus::Applied);
+    assert_eq!(
+        result.validator_address,
+        harness.alloy_key.address().to_string()
+    );
+    assert!(!result.config_hash.is_empty());
+    assert_eq!(result.agent_image, blueprint::operator::DEFAULT_AGENT_IMAGE);
     assert_eq!(results.service_id, service_id);
 
+    // The same configs again leave the running validator alone
+    let call = harness
+        .submit_job(service_id, 0, vec![config_urls, origin_chain_name])
+        .await?;
+    let results = harness.wait_for_job_execution(0, call).await?;
+    let unchanged: blueprint::SetConfigResult = from_field(results.result[0].clone())?;
+    assert_eq!(unchanged.status, blueprint::ConfigStatus::Unchanged);
+    assert_eq!(unchanged.config_hash, result.config_hash);
+
     sdk::info!("Validator running, starting relayer...");
     spinup_relayer(
         &origin_testnet,
@@ -503,3 +571,379 @@ async fn validator_test_inner() -> Result<()> {
 
     Ok(())
 }
+
+const MINIO_USER: &str = "minioadmin";
+const MINIO_PASSWORD: &str = "minioadmin";
+const MINIO_PORT: u16 = 9000;
+const SIGNATURES_BUCKET: &str = "signatures";
+const SIGNATURES_FOLDER: &str = "testnet1";
+
+async fn start_minio() -> Result<ContainerAsync<GenericImage>> {
+    let container = GenericImage::new("minio/minio", "RELEASE.2025-04-22T22-12-26Z")
+        .with_exposed_port(MINIO_PORT.tcp())
+        .with_wait_for(WaitFor::message_on_stdout("API:"))
+        .with_env_var("MINIO_ROOT_USER", MINIO_USER)
+        .with_env_var("MINIO_ROOT_PASSWORD", MINIO_PASSWORD)
+        .with_cmd(["server", "/data"])
+        .start()
+        .await?;
+
+    Ok(container)
+}
+
+fn s3_client(endpoint_url: &str) -> aws_sdk_s3::Client {
+    let config = aws_sdk_s3::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new("us-east-1"))
+        .endpoint_url(endpoint_url)
+        .credentials_provider(Credentials::new(
+            MINIO_USER,
+            MINIO_PASSWORD,
+            None,
+            None,
+            "test",
+        ))
+        .force_path_style(true)
+        .build();
+
+    aws_sdk_s3::Client::from_conf(config)
+}
+
+/// Connect `container_id` to the validator's Docker network, returning its IP there
+async fn join_validator_network(container_id: &str) -> Result<String> {
+    let connection = DockerBuilder::new().await?;
+    connection
+        .client()
+        .connect_network(
+            VALIDATOR_NETWORK_NAME,
+            ConnectNetworkOptions {
+                container: container_id,
+                ..Default::default()
+            },
+        )
+        .await?;
+    let inspect = connection
+        .client()
+        .inspect_container(container_id, None)
+        .await?;
+
+    Ok(
+        inspect.network_settings.unwrap().networks.unwrap()[VALIDATOR_NETWORK_NAME]
+            .ip_address
+            .clone()
+            .unwrap(),
+    )
+}
+
+async fn s3_checkpoint_syncer_test_inner() -> Result<()> {
+    let (origin_testnet, dest_testnet) = spinup_anvil_testnets().await?;
+
+    let (testnet1_docker_rpc_url, testnet1_host_rpc_url) = rpc_urls(&origin_testnet).await?;
+    let (testnet2_docker_rpc_url, testnet2_host_rpc_url) = rpc_urls(&dest_testnet).await?;
+
+    // The validator reaches MinIO over its Docker network, the test itself over the host
+    let minio = start_minio().await?;
+    let minio_docker_ip = join_validator_network(minio.id()).await?;
+
+    let s3 = s3_client(&format!(
+        "http://127.0.0.1:{}",
+        minio.get_host_port_ipv4(MINIO_PORT).await?
+    ));
+    s3.create_bucket().bucket(SIGNATURES_BUCKET).send().await?;
+
+    let tempdir = setup_temp_dir(
+        (testnet1_docker_rpc_url, testnet1_host_rpc_url.clone()),
+        (testnet2_docker_rpc_url, testnet2_host_rpc_url),
+    )?;
+    let temp_dir_path = tempdir.path().to_path_buf();
+
+    // The operator provides the credentials, the service owner only picks the bucket
+    fs::write(
+        temp_dir_path.join(blueprint::operator::OperatorConfig::FILE_NAME),
+        serde_json::to_vec(&serde_json::json!({
+            "signer": { "type": "keystore" },
+            "checkpoint_storage": {
+                "s3": {
+                    "credentials": {
+                        "access_key_id": MINIO_USER,
+                        "secret_access_key": MINIO_PASSWORD,
+                    },
+                    "endpoint_url": format!("http://{minio_docker_ip}:{MINIO_PORT}"),
+                }
+            }
+        }))?,
+    )?;
+
+    let harness = TangleTestHarness::setup(tempdir).await?;
+
+    let (mut test_env, service_id, _) = harness.setup_services::<1>(false).await?;
+    test_env.initialize().await?;
+    test_env
+        .add_job(blueprint::set_config.layer(TangleLayer))
+        .await;
+    test_env
+        .add_job(blueprint::set_checkpoint_syncer.layer(TangleLayer))
+        .await;
+
+    let ctx =
+        blueprint::HyperlaneContext::new(harness.env().clone(), temp_dir_path.clone()).await?;
+
+    test_env.start(ctx).await?;
+
+    let checkpoint_syncer = serde_json::json!({
+        "type": "s3",
+        "bucket": SIGNATURES_BUCKET,
+        "region": "us-east-1",
+        "folder": SIGNATURES_FOLDER,
+    });
+    let call = harness
+        .submit_job(
+            service_id,
+            blueprint::SET_CHECKPOINT_SYNCER_JOB_ID,
+            vec![
+                to_field(String::from("testnet1"))?,
+                to_field(checkpoint_syncer.to_string())?,
+            ],
+        )
+        .await?;
+    let results = harness.wait_for_job_execution(0, call).await?;
+    harness.verify_job(&results, vec![OutputValue::Uint64(0)]);
+
+    let agent_config_path = std::path::absolute(temp_dir_path.join("agent-config.json"))?;
+    let config_urls = to_field(Some(vec![format!(
+        "file://{}",
+        agent_config_path.display()
+    )]))?;
+    let call = harness
+        .submit_job(
+            service_id,
+            blueprint::SET_CONFIG_JOB_ID,
+            vec![config_urls, to_field(String::from("testnet1"))?],
+        )
+        .await?;
+    let results = harness.wait_for_job_execution(0, call).await?;
+    let result: blueprint::SetConfigResult = from_field(results.result[0].clone())?;
+    assert_eq!(result.status, blueprint::ConfigStatus::Applied);
+
+    sdk::info!("Validator running, dispatching a message to be signed...");
+    let (_testnet1_wallet, testnet1_provider) = wallet_for(
+        &hex::encode(harness.alloy_key.to_bytes()),
+        &testnet1_host_rpc_url,
+    );
+    let testnet1_mailbox = Mailbox::new(TESTNET1_MAILBOX, testnet1_provider);
+    let receipt = testnet1_mailbox
+        .dispatch_2(31338, Address::ZERO.into_word(), Bytes::from(MESSAGE))
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+    if !receipt.status() {
+        return Err(Report::msg("Failed to dispatch message"));
+    }
+
+    mine_block(&testnet1_host_rpc_url).await?;
+
+    // Both the announcement and a signed checkpoint should end up in the bucket
+    let timeout_result = tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            let objects = s3
+                .list_objects_v2()
+                .bucket(SIGNATURES_BUCKET)
+                .prefix(format!("{SIGNATURES_FOLDER}/"))
+                .send()
+                .await?;
+            let keys = objects
+                .contents()
+                .iter()
+                .filter_map(|object| object.key())
+                .collect::<Vec<_>>();
+            sdk::debug!("Objects in bucket: {keys:?}");
+
+            let announced = keys.iter().any(|key| key.ends_with("/announcement.json"));
+            let signed = keys
+                .iter()
+                .any(|key| key.ends_with("/checkpoint_latest_index.json"));
+            if announced && signed {
+                return Ok::<_, Report>(());
+            }
+
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+    })
+    .await;
+
+    match timeout_result {
+        Ok(res) => res,
+        Err(_) => Err(Report::msg(
+            "The validator never published its signatures to the bucket",
+        )),
+    }
+}
+
+const LOCALSTACK_PORT: u16 = 4566;
+const KMS_REGION: &str = "us-east-1";
+
+async fn start_localstack() -> Result<ContainerAsync<GenericImage>> {
+    let container = GenericImage::new("localstack/localstack", "3.8")
+        .with_exposed_port(LOCALSTACK_PORT.tcp())
+        .with_wait_for(WaitFor::message_on_stdout("Ready."))
+        .with_env_var("SERVICES", "kms")
+        .start()
+        .await?;
+
+    Ok(container)
+}
+
+/// Create a validator key in the KMS at `endpoint_url`, returning its ID
+async fn create_kms_key(endpoint_url: &str) -> Result<String> {
+    let config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(aws_config::Region::new(KMS_REGION))
+        .endpoint_url(endpoint_url)
+        .credentials_provider(Credentials::new("test", "test", None, None, "test"))
+        .load()
+        .await;
+
+    let key = aws_sdk_kms::Client::new(&config)
+        .create_key()
+        .key_spec(KeySpec::EccSecgP256K1)
+        .key_usage(KeyUsageType::SignVerify)
+        .send()
+        .await?;
+
+    Ok(key.key_metadata().unwrap().key_id().to_string())
+}
+
+async fn kms_signer_test_inner() -> Result<()> {
+    let (origin_testnet, dest_testnet) = spinup_anvil_testnets().await?;
+
+    let (testnet1_docker_rpc_url, testnet1_host_rpc_url) = rpc_urls(&origin_testnet).await?;
+    let (testnet2_docker_rpc_url, testnet2_host_rpc_url) = rpc_urls(&dest_testnet).await?;
+
+    // The agent reaches LocalStack over its Docker network. The blueprint shares the same
+    // `endpoint_url`, which the host can route to.
+    let localstack = start_localstack().await?;
+    let localstack_docker_ip = join_validator_network(localstack.id()).await?;
+    let kms_endpoint_url = format!("http://{localstack_docker_ip}:{LOCALSTACK_PORT}");
+
+    let key_id = create_kms_key(&format!(
+        "http://127.0.0.1:{}",
+        localstack.get_host_port_ipv4(LOCALSTACK_PORT).await?
+    ))
+    .await?;
+    let kms_signer = blueprint::signer::AwsKmsSigner {
+        key_id,
+        region: String::from(KMS_REGION),
+        endpoint_url: Some(kms_endpoint_url),
+        credentials: Some(blueprint::signer::AwsCredentials {
+            access_key_id: String::from("test"),
+            secret_access_key: String::from("test"),
+        }),
+    };
+    let kms_address = kms_signer.address().await?;
+
+    // The KMS key pays for its own announcement
+    Command::new("cast")
+        .args([
+            "rpc",
+            "anvil_setBalance",
+            &kms_address.to_string(),
+            "0xDE0B6B3A7640000",
+            "--rpc-url",
+            &testnet1_host_rpc_url,
+        ])
+        .output()?;
+
+    let tempdir = setup_temp_dir(
+        (testnet1_docker_rpc_url, testnet1_host_rpc_url),
+        (testnet2_docker_rpc_url, testnet2_host_rpc_url),
+    )?;
+    let temp_dir_path = tempdir.path().to_path_buf();
+
+    fs::write(
+        temp_dir_path.join(blueprint::operator::OperatorConfig::FILE_NAME),
+        serde_json::to_vec(&serde_json::json!({
+            "signer": blueprint::signer::ValidatorSigner::AwsKms(kms_signer),
+        }))?,
+    )?;
+
+    let harness = TangleTestHarness::setup(tempdir).await?;
+
+    let (mut test_env, service_id, _) = harness.setup_services::<1>(false).await?;
+    test_env.initialize().await?;
+    test_env
+        .add_job(blueprint::set_config.layer(TangleLayer))
+        .await;
+
+    let ctx =
+        blueprint::HyperlaneContext::new(harness.env().clone(), temp_dir_path.clone()).await?;
+
+    test_env.start(ctx).await?;
+
+    let agent_config_path = std::path::absolute(temp_dir_path.join("agent-config.json"))?;
+    let config_urls = to_field(Some(vec![format!(
+        "file://{}",
+        agent_config_path.display()
+    )]))?;
+    let call = harness
+        .submit_job(
+            service_id,
+            blueprint::SET_CONFIG_JOB_ID,
+            vec![config_urls, to_field(String::from("testnet1"))?],
+        )
+        .await?;
+    let results = harness.wait_for_job_execution(0, call).await?;
+
+    // Readiness only passes once the agent announced on-chain, signed with the KMS key
+    let result: blueprint::SetConfigResult = from_field(results.result[0].clone())?;
+    assert_eq!(result.status, blueprint::ConfigStatus::Applied);
+    assert_eq!(result.validator_address, kms_address.to_string());
+
+    // With default configs, KMS adds no config file either, so the agent mustn't get an empty
+    // `CONFIG_FILES`. The agent's defaults don't know `testnet2`, so only its environment is
+    // checked, as soon as its container is created.
+    let connection = DockerBuilder::new().await?;
+    let mut events = connection.client().events(Some(EventsOptions::<String> {
+        filters: HashMap::from([
+            (String::from("type"), vec![String::from("container")]),
+            (String::from("event"), vec![String::from("create")]),
+            (
+                String::from("label"),
+                vec![String::from("hyperlane.origin_chain=testnet2")],
+            ),
+        ]),
+        ..Default::default()
+    }));
+
+    let call = harness
+        .submit_job(
+            service_id,
+            blueprint::SET_CONFIG_JOB_ID,
+            vec![
+                to_field(None::<Vec<String>>)?,
+                to_field(String::from("testnet2"))?,
+            ],
+        )
+        .await?;
+
+    let event = tokio::time::timeout(Duration::from_secs(60), events.next())
+        .await?
+        .unwrap()?;
+    let container_id = event.actor.and_then(|actor| actor.id).unwrap();
+    let inspect = connection
+        .client()
+        .inspect_container(&container_id, None)
+        .await?;
+    let env = inspect.config.and_then(|config| config.env).unwrap();
+    assert!(
+        !env.iter().any(|var| var.starts_with("CONFIG_FILES=")),
+        "{env:?}"
+    );
+    assert!(
+        env.contains(&String::from("HYP_VALIDATOR_TYPE=aws")),
+        "{env:?}"
+    );
+
+    harness.wait_for_job_execution(0, call).await?;
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use hyperlane_validator_blueprint_lib::image::pull_error_message;
+
+const IMAGE: &str = "gcr.io/private/hyperlane-agent@sha256:00";
+
+#[test]
+fn auth_failure_points_at_registry_auth() {
+    for message in [
+        "unauthorized: authentication required",
+        "Head \"https://gcr.io/v2/private/hyperlane-agent/manifests/sha256:00\": denied: Permission denied",
+        "no basic auth credentials",
+    ] {
+        let err = pull_error_message(IMAGE, message).to_string();
+        assert!(err.contains("registry_auth"), "{err}");
+        assert!(err.contains(IMAGE), "{err}");
+        assert!(err.contains(message), "{err}");
+    }
+}
+
+#[test]
+fn other_failures_are_passed_through() {
+    let message = "manifest unknown: manifest unknown";
+    let err = pull_error_message(IMAGE, message).to_string();
+    assert!(!err.contains("registry_auth"), "{err}");
+    assert_eq!(err, format!("Failed to pull image `{IMAGE}`: {message}"));
+}
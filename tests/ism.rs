@@ -0,0 +1,136 @@
+use blueprint_sdk::alloy::primitives::Address;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use hyperlane_validator_blueprint_lib::ism::{self, MAX_ISMS, Module, MultisigIsm};
+use std::collections::HashMap;
+
+#[test]
+fn message_layout() {
+    let message = ism::message(31337, Some(31338));
+    assert_eq!(message.len(), 77);
+
+    // Version, then nonce
+    assert_eq!(message[0], 3);
+    assert_eq!(message[1..5], [0; 4]);
+    assert_eq!(message[5..9], 31337u32.to_be_bytes());
+    // Sender
+    assert_eq!(message[9..41], [0; 32]);
+    assert_eq!(message[41..45], 31338u32.to_be_bytes());
+    // Recipient
+    assert_eq!(message[45..77], [0; 32]);
+}
+
+#[test]
+fn message_without_destination_domain() {
+    let message = ism::message(31337, None);
+    assert_eq!(message.len(), 77);
+    assert_eq!(message[5..9], 31337u32.to_be_bytes());
+    assert_eq!(message[41..45], [0; 4]);
+}
+
+fn address(byte: u8) -> Address {
+    Address::repeat_byte(byte)
+}
+
+/// Resolve `root` against ISMs deployed as `modules`
+async fn resolve(root: Address, modules: &HashMap<Address, Module>) -> Result<Vec<MultisigIsm>> {
+    ism::resolve(root, |ism| async move {
+        modules
+            .get(&ism)
+            .cloned()
+            .ok_or_else(|| eyre!("No ISM at `{ism}`"))
+    })
+    .await
+}
+
+#[tokio::test]
+async fn multisig_default_ism() -> Result<()> {
+    let validators = vec![address(0xa1), address(0xa2)];
+    let modules = HashMap::from([(
+        address(1),
+        Module::Multisig {
+            threshold: 2,
+            validators: validators.clone(),
+        },
+    )]);
+
+    let isms = resolve(address(1), &modules).await?;
+    assert_eq!(
+        isms,
+        vec![MultisigIsm {
+            address: address(1),
+            threshold: 2,
+            validators,
+        }]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn routing_and_aggregation() -> Result<()> {
+    // Routing -> aggregation of two multisig ISMs and one that doesn't use validators
+    let modules = HashMap::from([
+        (address(1), Module::Routing(address(2))),
+        (
+            address(2),
+            Module::Aggregation(vec![address(3), address(4), address(5)]),
+        ),
+        (
+            address(3),
+            Module::Multisig {
+                threshold: 1,
+                validators: vec![address(0xa1)],
+            },
+        ),
+        (
+            address(4),
+            Module::Multisig {
+                threshold: 2,
+                validators: vec![address(0xa2), address(0xa3)],
+            },
+        ),
+        (address(5), Module::Other),
+    ]);
+
+    let isms = resolve(address(1), &modules).await?;
+    let mut addresses: Vec<_> = isms.iter().map(|ism| ism.address).collect();
+    addresses.sort();
+    assert_eq!(addresses, vec![address(3), address(4)]);
+
+    let validators: Vec<_> = isms.iter().flat_map(|ism| ism.validators.clone()).collect();
+    assert!(validators.contains(&address(0xa1)));
+    assert!(validators.contains(&address(0xa3)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn no_multisig_isms() -> Result<()> {
+    let modules = HashMap::from([
+        (address(1), Module::Routing(address(2))),
+        (address(2), Module::Other),
+    ]);
+
+    assert!(resolve(address(1), &modules).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn routing_loop() {
+    let modules = HashMap::from([
+        (address(1), Module::Routing(address(2))),
+        (address(2), Module::Routing(address(1))),
+    ]);
+
+    let err = resolve(address(1), &modules).await.unwrap_err();
+    assert!(err.to_string().contains(&MAX_ISMS.to_string()), "{err}");
+}
+
+#[tokio::test]
+async fn unreadable_ism() {
+    let modules = HashMap::from([(address(1), Module::Aggregation(vec![address(2)]))]);
+
+    let err = resolve(address(1), &modules).await.unwrap_err();
+    assert!(err.to_string().contains(&address(2).to_string()), "{err}");
+}
@@ -0,0 +1,46 @@
+use blueprint_sdk::tangle::extract::TangleResult;
+use blueprint_sdk::tangle::metadata::IntoTangleFieldTypes;
+use blueprint_sdk::tangle::metadata::macros::ext::FieldType;
+use blueprint_sdk::tangle::serde::{BoundedVec, Field, from_field, to_field};
+use hyperlane_validator_blueprint_lib::{ConfigStatus, SetConfigResult};
+
+#[test]
+fn set_config_result_is_a_struct() {
+    let fields =
+        <color_eyre::Result<TangleResult<SetConfigResult>> as IntoTangleFieldTypes>::into_tangle_fields();
+
+    // `status` is a unit enum, which is passed as its name
+    assert_eq!(
+        fields,
+        vec![FieldType::Struct(Box::new(BoundedVec(vec![
+            FieldType::String;
+            4
+        ])))]
+    );
+}
+
+#[test]
+fn set_config_result_round_trips() {
+    for status in [
+        ConfigStatus::Applied,
+        ConfigStatus::Unchanged,
+        ConfigStatus::Reverted,
+        ConfigStatus::Failed,
+    ] {
+        let result = SetConfigResult {
+            status,
+            validator_address: String::from("0x0000000000000000000000000000000000000001"),
+            config_hash: String::from("0x00"),
+            agent_image: String::from("agent@sha256:00"),
+        };
+
+        let field = to_field(result.clone()).unwrap();
+        let Field::Struct(_, fields) = &field else {
+            panic!("Expected a struct, got {field:?}");
+        };
+        assert!(matches!(fields.0[0].1, Field::String(_)));
+
+        let round_tripped: SetConfigResult = from_field(field).unwrap();
+        assert_eq!(round_tripped, result);
+    }
+}
@@ -0,0 +1,106 @@
+use blueprint_sdk::alloy::primitives::Address;
+use blueprint_sdk::crypto::sp_core::SpEcdsaPublic;
+use blueprint_sdk::testing::tempfile;
+use hyperlane_validator_blueprint_lib::key_rotation::{self, RotatedKeys};
+use std::path::Path;
+
+fn address(data_dir: &Path, key: &SpEcdsaPublic) -> Address {
+    let keystore = key_rotation::keystore(data_dir).unwrap();
+    key_rotation::signer(&keystore, key).unwrap().address()
+}
+
+#[test]
+fn rotation_waits_for_switch() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let key = key_rotation::rotate(dir.path()).unwrap();
+
+    // Validators keep their key until the new one is funded and switched to
+    assert!(RotatedKeys::load(dir.path()).unwrap().is_none());
+    let pending = key_rotation::pending(dir.path()).unwrap().unwrap();
+    assert_eq!(address(dir.path(), &pending), address(dir.path(), &key));
+
+    // Only one new key at a time
+    assert!(key_rotation::rotate(dir.path()).is_err());
+}
+
+#[test]
+fn switch_and_confirm() {
+    let dir = tempfile::tempdir().unwrap();
+
+    assert!(key_rotation::switch(dir.path()).is_err());
+
+    let first = key_rotation::rotate(dir.path()).unwrap();
+    let first_address = address(dir.path(), &first);
+    assert!(key_rotation::switch(dir.path()).unwrap().is_none());
+    assert!(key_rotation::pending(dir.path()).unwrap().is_none());
+
+    let keys = RotatedKeys::load(dir.path()).unwrap().unwrap();
+    assert_eq!(address(dir.path(), &keys.current), first_address);
+    assert!(keys.previous.is_none());
+    assert!(keys.pending_confirmation);
+
+    // Can't rotate again before confirming
+    assert!(key_rotation::rotate(dir.path()).is_err());
+
+    let confirmed = key_rotation::confirm(dir.path()).unwrap();
+    assert_eq!(address(dir.path(), &confirmed), first_address);
+    assert!(key_rotation::confirm(dir.path()).is_err());
+
+    // A second rotation keeps the first key until it's confirmed
+    let second = key_rotation::rotate(dir.path()).unwrap();
+    let second_address = address(dir.path(), &second);
+    let previous = key_rotation::switch(dir.path()).unwrap().unwrap();
+    assert_eq!(address(dir.path(), &previous.current), first_address);
+
+    let keys = RotatedKeys::load(dir.path()).unwrap().unwrap();
+    assert_eq!(address(dir.path(), &keys.current), second_address);
+    assert_eq!(
+        address(dir.path(), keys.previous.as_ref().unwrap()),
+        first_address
+    );
+
+    key_rotation::confirm(dir.path()).unwrap();
+    let keystore = key_rotation::keystore(dir.path()).unwrap();
+    assert!(key_rotation::signer(&keystore, &first).is_err());
+    assert!(
+        RotatedKeys::load(dir.path())
+            .unwrap()
+            .unwrap()
+            .previous
+            .is_none()
+    );
+}
+
+#[test]
+fn rollback_keeps_new_key_pending() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // First rotation, away from the operator config's key
+    let key = key_rotation::rotate(dir.path()).unwrap();
+    let previous = key_rotation::switch(dir.path()).unwrap();
+    key_rotation::rollback(dir.path(), previous).unwrap();
+
+    assert!(RotatedKeys::load(dir.path()).unwrap().is_none());
+    let pending = key_rotation::pending(dir.path()).unwrap().unwrap();
+    assert_eq!(address(dir.path(), &pending), address(dir.path(), &key));
+
+    // Once funded, the switch can be retried
+    key_rotation::switch(dir.path()).unwrap();
+    key_rotation::confirm(dir.path()).unwrap();
+
+    // Rolling back a later rotation returns to the confirmed key
+    let confirmed = RotatedKeys::load(dir.path()).unwrap().unwrap();
+    let next = key_rotation::rotate(dir.path()).unwrap();
+    let previous = key_rotation::switch(dir.path()).unwrap();
+    key_rotation::rollback(dir.path(), previous).unwrap();
+
+    let keys = RotatedKeys::load(dir.path()).unwrap().unwrap();
+    assert_eq!(
+        address(dir.path(), &keys.current),
+        address(dir.path(), &confirmed.current)
+    );
+    assert!(!keys.pending_confirmation);
+    let pending = key_rotation::pending(dir.path()).unwrap().unwrap();
+    assert_eq!(address(dir.path(), &pending), address(dir.path(), &next));
+}
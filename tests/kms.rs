@@ -0,0 +1,106 @@
+//! Tests for the AWS KMS signer, against LocalStack's KMS
+
+use aws_sdk_kms::types::{KeySpec, KeyUsageType};
+use blueprint_sdk::alloy::signers::Signer;
+use color_eyre::Result;
+use hyperlane_validator_blueprint_lib::signer::{AwsCredentials, AwsKmsSigner};
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+
+const REGION: &str = "us-east-1";
+const LOCALSTACK_PORT: u16 = 4566;
+
+async fn start_localstack() -> Result<(ContainerAsync<GenericImage>, String)> {
+    let container = GenericImage::new("localstack/localstack", "3.8")
+        .with_exposed_port(LOCALSTACK_PORT.tcp())
+        .with_wait_for(WaitFor::message_on_stdout("Ready."))
+        .with_env_var("SERVICES", "kms")
+        .start()
+        .await?;
+
+    let port = container.get_host_port_ipv4(LOCALSTACK_PORT).await?;
+    Ok((container, format!("http://127.0.0.1:{port}")))
+}
+
+fn signer_for(key_id: String, endpoint_url: &str) -> AwsKmsSigner {
+    AwsKmsSigner {
+        key_id,
+        region: String::from(REGION),
+        endpoint_url: Some(endpoint_url.to_string()),
+        credentials: Some(AwsCredentials {
+            access_key_id: String::from("test"),
+            secret_access_key: String::from("test"),
+        }),
+    }
+}
+
+/// Create a key the way an operator would for a Hyperlane validator
+async fn create_validator_key(endpoint_url: &str) -> Result<String> {
+    let config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(aws_config::Region::new(REGION))
+        .endpoint_url(endpoint_url)
+        .credentials_provider(aws_sdk_kms::config::Credentials::new(
+            "test", "test", None, None, "test",
+        ))
+        .load()
+        .await;
+
+    let kms = aws_sdk_kms::Client::new(&config);
+    let key = kms
+        .create_key()
+        .key_spec(KeySpec::EccSecgP256K1)
+        .key_usage(KeyUsageType::SignVerify)
+        .send()
+        .await?;
+
+    Ok(key.key_metadata().unwrap().key_id().to_string())
+}
+
+#[test]
+fn kms_agent_env() {
+    let signer = signer_for(String::from("alias/validator"), "http://localstack:4566");
+    assert_eq!(
+        signer.agent_env(),
+        [
+            "HYP_VALIDATOR_TYPE=aws",
+            "HYP_VALIDATOR_ID=alias/validator",
+            "HYP_VALIDATOR_REGION=us-east-1",
+            "AWS_ENDPOINT_URL_KMS=http://localstack:4566",
+        ]
+    );
+
+    // Credentials never end up in the environment
+    let mut signer = signer;
+    signer.endpoint_url = None;
+    let env = signer.agent_env();
+    assert_eq!(env.len(), 3);
+    assert!(env.iter().all(|var| !var.contains("test")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn kms_signer_address() -> Result<()> {
+    let (_localstack, endpoint_url) = start_localstack().await?;
+    let key_id = create_validator_key(&endpoint_url).await?;
+
+    let signer = signer_for(key_id, &endpoint_url);
+    let address = signer.address().await?;
+
+    // Signatures made with the key must recover to the address the validator reports
+    const MESSAGE: &[u8] = b"hyperlane checkpoint";
+    let signature = signer.connect().await?.sign_message(MESSAGE).await?;
+    assert_eq!(signature.recover_address_from_msg(MESSAGE)?, address);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn kms_signer_missing_key() -> Result<()> {
+    let (_localstack, endpoint_url) = start_localstack().await?;
+
+    let signer = signer_for(String::from("alias/does-not-exist"), &endpoint_url);
+    let err = signer.address().await.unwrap_err();
+    assert!(err.to_string().contains("alias/does-not-exist"), "{err}");
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use blueprint_sdk::alloy::primitives::U256;
+use blueprint_sdk::testing::tempfile;
+use hyperlane_validator_blueprint_lib::operator::{
+    DEFAULT_AGENT_IMAGE, LowBalanceAction, OperatorConfig,
+};
+use hyperlane_validator_blueprint_lib::signer::ValidatorSigner;
+use std::fs;
+
+#[test]
+fn missing_file_uses_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = OperatorConfig::load(&dir.path().join(OperatorConfig::FILE_NAME)).unwrap();
+    assert_eq!(config, OperatorConfig::default());
+
+    // An empty account can never announce
+    assert_eq!(config.min_validator_balance, U256::from(1));
+    assert_eq!(config.low_balance, LowBalanceAction::Fail);
+
+    // Existing validators keep their address, derived keys are opt-in
+    assert_eq!(config.signer, ValidatorSigner::Keystore);
+}
+
+#[test]
+fn min_validator_balance() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(OperatorConfig::FILE_NAME);
+    fs::write(
+        &path,
+        r#"{ "min_validator_balance": "1000000000000000000", "low_balance": "warn" }"#,
+    )
+    .unwrap();
+
+    let config = OperatorConfig::load(&path).unwrap();
+    assert_eq!(
+        config.min_validator_balance,
+        U256::from(1_000_000_000_000_000_000_u64)
+    );
+    assert_eq!(config.low_balance, LowBalanceAction::Warn);
+}
+
+#[test]
+fn agent_versions_must_be_pinned() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(OperatorConfig::FILE_NAME);
+
+    fs::write(
+        &path,
+        r#"{ "agent_versions": { "agents-v1.2.0": "gcr.io/abacus-labs-dev/hyperlane-agent:agents-v1.2.0" } }"#,
+    )
+    .unwrap();
+    let err = OperatorConfig::load(&path).unwrap_err();
+    assert!(err.to_string().contains("agents-v1.2.0"));
+
+    let pinned = format!(
+        "gcr.io/abacus-labs-dev/hyperlane-agent@sha256:{}",
+        "a".repeat(64)
+    );
+    fs::write(
+        &path,
+        format!(r#"{{ "agent_versions": {{ "agents-v1.2.0": "{pinned}" }} }}"#),
+    )
+    .unwrap();
+    let config = OperatorConfig::load(&path).unwrap();
+    assert_eq!(config.agent_versions["agents-v1.2.0"], pinned);
+}
+
+#[test]
+fn agent_image_must_be_pinned() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(OperatorConfig::FILE_NAME);
+
+    fs::write(
+        &path,
+        r#"{ "agent_image": "gcr.io/abacus-labs-dev/hyperlane-agent:agents-v1.3.0" }"#,
+    )
+    .unwrap();
+    let err = OperatorConfig::load(&path).unwrap_err();
+    assert!(err.to_string().contains("agents-v1.3.0"));
+
+    let pinned = format!(
+        "gcr.io/abacus-labs-dev/hyperlane-agent@sha256:{}",
+        "a".repeat(64)
+    );
+    fs::write(&path, format!(r#"{{ "agent_image": "{pinned}" }}"#)).unwrap();
+    assert_eq!(OperatorConfig::load(&path).unwrap().agent_image, pinned);
+
+    // Naming the default explicitly is the same as leaving it out
+    fs::write(
+        &path,
+        format!(r#"{{ "agent_image": "{DEFAULT_AGENT_IMAGE}" }}"#),
+    )
+    .unwrap();
+    assert_eq!(
+        OperatorConfig::load(&path).unwrap().agent_image,
+        DEFAULT_AGENT_IMAGE
+    );
+}
@@ -0,0 +1,89 @@
+use hyperlane_validator_blueprint_lib::readiness::{LogWatcher, RECENT_LINES, Readiness};
+
+const STARTUP: &[&str] = &[
+    "INFO hyperlane_base::settings: Loading agent settings",
+    "INFO validator::validator: Checkpoint syncer is ready",
+    "INFO validator::validator: Waiting for first message in merkle tree hook",
+];
+
+fn watch(lines: &[&str]) -> (LogWatcher, Readiness) {
+    let mut watcher = LogWatcher::default();
+    let mut readiness = Readiness::Pending;
+    for line in lines {
+        readiness = watcher.push(line);
+        if readiness != Readiness::Pending {
+            break;
+        }
+    }
+
+    (watcher, readiness)
+}
+
+#[test]
+fn announced() {
+    let mut lines = STARTUP.to_vec();
+    lines.push(
+        "INFO validator::validator: Validator has announced signature storage location, location: \"file:///checkpoints\"",
+    );
+
+    let (_, readiness) = watch(&lines);
+    assert_eq!(readiness, Readiness::Ready);
+}
+
+#[test]
+fn signing() {
+    let mut lines = STARTUP.to_vec();
+    lines.push("INFO validator::submit: Signed all queued checkpoints until index, index: 4");
+
+    let (_, readiness) = watch(&lines);
+    assert_eq!(readiness, Readiness::Ready);
+}
+
+#[test]
+fn needs_funds() {
+    let mut lines = STARTUP.to_vec();
+    lines.push(
+        "WARN validator::validator: Please send tokens to your chain signer address to announce, chain_signer: 0x0000000000000000000000000000000000000001",
+    );
+
+    let (watcher, readiness) = watch(&lines);
+    let Readiness::Failed(reason) = readiness else {
+        panic!("Expected a failure, got {readiness:?}");
+    };
+    assert!(reason.contains("funds"), "{reason}");
+
+    // The failure report includes the line that caused it
+    let err = watcher.failure(&reason).to_string();
+    assert!(err.contains("Please send tokens"), "{err}");
+}
+
+#[test]
+fn announce_reverted() {
+    let (_, readiness) = watch(&[
+        "ERROR validator::validator: Transaction attempting to announce validator reverted",
+    ]);
+    assert!(matches!(readiness, Readiness::Failed(_)), "{readiness:?}");
+}
+
+#[test]
+fn timeout_keeps_recent_lines() {
+    let lines: Vec<String> = (0..RECENT_LINES * 2)
+        .map(|i| format!("INFO validator::validator: Waiting for first message, attempt: {i}"))
+        .collect();
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+
+    // Nothing conclusive, so the wait runs into its timeout
+    let (watcher, readiness) = watch(&lines);
+    assert_eq!(readiness, Readiness::Pending);
+
+    assert_eq!(watcher.recent(), lines[RECENT_LINES..].to_vec());
+    let err = watcher
+        .failure("no sign of the validator running within 120s")
+        .to_string();
+    assert!(err.contains("within 120s"), "{err}");
+    assert!(!err.contains("attempt: 0\n"), "{err}");
+    assert!(
+        err.ends_with(&format!("attempt: {}", RECENT_LINES * 2 - 1)),
+        "{err}"
+    );
+}
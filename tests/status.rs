@@ -0,0 +1,47 @@
+use blueprint_sdk::testing::tempfile;
+use color_eyre::Result;
+use docktopus::DockerBuilder;
+use hyperlane_validator_blueprint_lib::status;
+use testcontainers::core::Mount;
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{GenericImage, ImageExt};
+
+const CHECKPOINTS_PATH: &str = "/checkpoints";
+
+#[tokio::test]
+async fn container_lifecycle() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let container = GenericImage::new("busybox", "1.36")
+        .with_cmd(["sleep", "infinity"])
+        .with_mount(Mount::bind_mount(
+            dir.path().display().to_string(),
+            CHECKPOINTS_PATH,
+        ))
+        .start()
+        .await?;
+    let connection = DockerBuilder::new().await?;
+    let docker = connection.client();
+    let id = container.id().to_string();
+
+    let state = status::container_state(&docker, &id).await.unwrap();
+    assert_eq!(state.state, "running");
+    assert!(state.uptime_secs.is_some());
+    assert_eq!(state.image.as_deref(), Some("busybox:1.36"));
+
+    assert!(status::has_mount(&docker, &id, dir.path(), CHECKPOINTS_PATH).await);
+    assert!(!status::has_mount(&docker, &id, dir.path(), "/elsewhere").await);
+    let other = tempfile::tempdir()?;
+    assert!(!status::has_mount(&docker, &id, other.path(), CHECKPOINTS_PATH).await);
+
+    // Stopped containers have no uptime
+    container.stop().await?;
+    let state = status::container_state(&docker, &id).await.unwrap();
+    assert_eq!(state.state, "exited");
+    assert!(state.uptime_secs.is_none());
+
+    container.rm().await?;
+    assert!(status::container_state(&docker, &id).await.is_none());
+    assert!(!status::has_mount(&docker, &id, dir.path(), CHECKPOINTS_PATH).await);
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use blueprint_sdk::alloy::primitives::{B256, b256};
+use blueprint_sdk::alloy::signers::local::PrivateKeySigner;
+use blueprint_sdk::testing::tempfile;
+use hyperlane_validator_blueprint_lib::config::{AgentConfig, SignerConfig};
+use hyperlane_validator_blueprint_lib::signer::{
+    self, VALIDATOR_SECRET_FILE, derive_validator_key,
+};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::str::FromStr;
+
+const OPERATOR_KEY: B256 =
+    b256!("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80");
+
+#[test]
+fn derivation_is_deterministic() {
+    let first = derive_validator_key(&OPERATOR_KEY, 0, 1);
+    let second = derive_validator_key(&OPERATOR_KEY, 0, 1);
+    assert_eq!(first.address(), second.address());
+}
+
+#[test]
+fn keys_are_unique_per_service() {
+    let service_1 = derive_validator_key(&OPERATOR_KEY, 0, 1);
+    let service_2 = derive_validator_key(&OPERATOR_KEY, 0, 2);
+    let other_blueprint = derive_validator_key(&OPERATOR_KEY, 1, 1);
+
+    assert_ne!(service_1.address(), service_2.address());
+    assert_ne!(service_1.address(), other_blueprint.address());
+}
+
+#[test]
+fn key_differs_from_operator_key() {
+    let operator = PrivateKeySigner::from_bytes(&OPERATOR_KEY).unwrap();
+    let validator = derive_validator_key(&OPERATOR_KEY, 0, 1);
+    assert_ne!(operator.address(), validator.address());
+}
+
+#[test]
+fn keys_are_unique_per_operator() {
+    let mut other_operator_key = OPERATOR_KEY;
+    other_operator_key.0[31] ^= 1;
+
+    let validator = derive_validator_key(&OPERATOR_KEY, 0, 1);
+    let other_validator = derive_validator_key(&other_operator_key, 0, 1);
+    assert_ne!(validator.address(), other_validator.address());
+}
+
+#[test]
+fn key_secret() {
+    let dir = tempfile::tempdir().unwrap();
+    let key = derive_validator_key(&OPERATOR_KEY, 0, 1);
+
+    let config_files = signer::write_key_secret(&key, dir.path()).unwrap();
+    assert_eq!(config_files, [format!("/secrets/{VALIDATOR_SECRET_FILE}")]);
+
+    // Only the blueprint's user can read the key
+    let secret_path = dir.path().join(VALIDATOR_SECRET_FILE);
+    let mode = fs::metadata(&secret_path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    let config: AgentConfig =
+        serde_json::from_str(&fs::read_to_string(&secret_path).unwrap()).unwrap();
+    let Some(SignerConfig::HexKey { key: hex_key }) = config.validator else {
+        panic!("Expected a hex key signer");
+    };
+    assert_eq!(
+        PrivateKeySigner::from_str(&hex_key).unwrap().address(),
+        key.address()
+    );
+
+    signer::remove_key_secret(dir.path()).unwrap();
+    assert!(!secret_path.exists());
+}